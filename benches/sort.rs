@@ -0,0 +1,73 @@
+// Benchmarks require a nightly compiler and the `unstable` feature:
+// cargo +nightly bench --features unstable
+#![cfg_attr(feature = "unstable", feature(test))]
+#![cfg(feature = "unstable")]
+extern crate ord_subset;
+extern crate test;
+
+use ord_subset::OrdSubsetSliceExt;
+use test::Bencher;
+
+// Deterministic pseudo random floats with every `nan_every`th value a NaN
+fn floats(n: usize, nan_every: usize) -> Vec<f64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..n)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            match i % nan_every {
+                0 => std::f64::NAN,
+                _ => (state >> 11) as f64 - (1u64 << 52) as f64,
+            }
+        })
+        .collect()
+}
+
+fn no_nan() -> Vec<f64> {
+    floats(10_000, usize::max_value())
+}
+
+fn one_percent_nan() -> Vec<f64> {
+    floats(10_000, 100)
+}
+
+#[bench]
+fn sort_no_nan(b: &mut Bencher) {
+    let data = no_nan();
+    b.iter(|| {
+        let mut v = data.clone();
+        v.ord_subset_sort();
+        v
+    })
+}
+
+#[bench]
+fn sort_one_percent_nan(b: &mut Bencher) {
+    let data = one_percent_nan();
+    b.iter(|| {
+        let mut v = data.clone();
+        v.ord_subset_sort();
+        v
+    })
+}
+
+#[bench]
+fn sort_unstable_no_nan(b: &mut Bencher) {
+    let data = no_nan();
+    b.iter(|| {
+        let mut v = data.clone();
+        v.ord_subset_sort_unstable();
+        v
+    })
+}
+
+#[bench]
+fn sort_unstable_one_percent_nan(b: &mut Bencher) {
+    let data = one_percent_nan();
+    b.iter(|| {
+        let mut v = data.clone();
+        v.ord_subset_sort_unstable();
+        v
+    })
+}
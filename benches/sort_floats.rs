@@ -0,0 +1,45 @@
+// Benchmarks require a nightly compiler and the `unstable` feature:
+// cargo +nightly bench --features unstable
+#![cfg_attr(feature = "unstable", feature(test))]
+#![cfg(feature = "unstable")]
+extern crate ord_subset;
+extern crate test;
+
+use ord_subset::OrdSubsetSliceExt;
+use test::Bencher;
+
+// Deterministic pseudo random floats with every 16th value a NaN
+fn floats(n: usize) -> Vec<f64> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..n)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            match i % 16 {
+                0 => std::f64::NAN,
+                _ => (state >> 11) as f64 - (1u64 << 52) as f64,
+            }
+        })
+        .collect()
+}
+
+#[bench]
+fn sort_unstable(b: &mut Bencher) {
+    let data = floats(10_000);
+    b.iter(|| {
+        let mut v = data.clone();
+        v.ord_subset_sort_unstable();
+        v
+    })
+}
+
+#[bench]
+fn sort_floats(b: &mut Bencher) {
+    let data = floats(10_000);
+    b.iter(|| {
+        let mut v = data.clone();
+        v.ord_subset_sort_floats();
+        v
+    })
+}
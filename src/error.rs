@@ -0,0 +1,35 @@
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0 or the MIT license
+// http://opensource.org/licenses/MIT, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+
+pub(crate) static ERROR_BINARY_SEARCH_OUTSIDE_ORDER: &str =
+    "Attempted binary search for value outside total order";
+pub(crate) static ERROR_BINARY_SEARCH_EXPECT: &str = "Unexpected None for a.partial_cmp(b), a,b inside order. Violated OrdSubset contract or attempted binary search on unsorted data";
+#[cfg(feature = "std")]
+pub(crate) static ERROR_QUANTILE_OUT_OF_RANGE: &str = "Quantile is not within [0, 1]";
+
+/// An error which can be returned by the `try_ord_subset_binary_search*` methods of `OrdSubsetSliceExt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchError {
+    /// The searched value is outside the total order.
+    NeedleUnordered,
+    /// `a.partial_cmp(b)` returned `None` for two values `a`,`b` inside the total order.
+    /// Either the `OrdSubset` contract is violated or the slice isn't sorted.
+    ContractViolation,
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SearchError::NeedleUnordered => f.write_str(ERROR_BINARY_SEARCH_OUTSIDE_ORDER),
+            SearchError::ContractViolation => f.write_str(ERROR_BINARY_SEARCH_EXPECT),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for SearchError {}
@@ -0,0 +1,61 @@
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0 or the MIT license
+// http://opensource.org/licenses/MIT, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ord_subset_trait::*;
+
+macro_rules! finite_float {
+    ($(#[$attr:meta])* $Name:ident, $T:ident) => {
+        $(#[$attr])*
+        #[derive(PartialEq, PartialOrd, Clone, Copy, Debug, Default)]
+        pub struct $Name(pub $T);
+
+        impl OrdSubset for $Name {
+            #[inline(always)]
+            fn is_outside_order(&self) -> bool {
+                !self.0.is_finite()
+            }
+        }
+
+        impl From<$T> for $Name {
+            #[inline(always)]
+            fn from(x: $T) -> Self {
+                $Name(x)
+            }
+        }
+
+        impl From<$Name> for $T {
+            #[inline(always)]
+            fn from(x: $Name) -> Self {
+                x.0
+            }
+        }
+    };
+}
+
+finite_float!(
+    /// An `f64` for which infinities are outside the total order, just like NaN.
+    ///
+    /// The `OrdSubset` impl of `f64` treats `±∞` as ordinary values. Wrap floats in `FiniteF64` to
+    /// have the sorts put infinities at the end and the binary searches reject them.
+    ///
+    /// ```
+    /// use ord_subset::{FiniteF64, OrdSubsetSliceExt};
+    /// use std::f64;
+    ///
+    /// let mut v = [3.0, f64::INFINITY, 1.0, f64::NAN, f64::NEG_INFINITY].map(FiniteF64);
+    /// v.ord_subset_sort_unstable();
+    /// assert_eq!(v[..2], [FiniteF64(1.0), FiniteF64(3.0)]);
+    /// assert!(v[2..].iter().all(|x| !x.0.is_finite()));
+    /// ```
+    FiniteF64,
+    f64
+);
+
+finite_float!(
+    /// An `f32` for which infinities are outside the total order, just like NaN. See `FiniteF64`.
+    FiniteF32,
+    f32
+);
@@ -6,6 +6,8 @@
 
 use ord_subset_trait::*;
 use ord_var::*;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
 
 /////////////////////////////////////////////////////////////////////
 pub trait OrdSubsetIterExt: Iterator //where Self::Item: OrdSubset
@@ -102,6 +104,192 @@ pub trait OrdSubsetIterExt: Iterator //where Self::Item: OrdSubset
         // Some > None, always
         self.max_by_key(|it| OrdVar::new_checked(f(it)))
     }
+
+    /// Returns the element that gives the maximum value from the specified function together with that value.
+    /// Values outside the ordered subset as given by `.is_outside_order()` on the mapped value are ignored.
+    ///
+    /// The key function is called exactly once per element, the key of the result doesn't need to be recomputed.
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetIterExt;
+    ///
+    /// let words = ["1.5", "x", "2.5", "-3"];
+    /// let max = words.iter().ord_subset_max_by_key_with_key(|w| w.parse().unwrap_or(std::f64::NAN));
+    /// assert_eq!(max, Some((&"2.5", 2.5)));
+    /// ```
+    #[inline]
+    fn ord_subset_max_by_key_with_key<F, B>(self, mut f: F) -> Option<(Self::Item, B)>
+    where
+        F: FnMut(&Self::Item) -> B,
+        B: OrdSubset,
+        Self: Sized,
+    {
+        self.filter_map(|it| OrdVar::new_checked(f(&it)).map(|key| (it, key)))
+            .max_by(|a, b| a.1.cmp(&b.1))
+            .map(|(it, key)| (it, key.into_inner()))
+    }
+
+    /// Returns the element that gives the minimum value from the specified function together with that value.
+    /// Values outside the ordered subset as given by `.is_outside_order()` on the mapped value are ignored.
+    ///
+    /// The key function is called exactly once per element, the key of the result doesn't need to be recomputed.
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    #[inline]
+    fn ord_subset_min_by_key_with_key<F, B>(self, mut f: F) -> Option<(Self::Item, B)>
+    where
+        F: FnMut(&Self::Item) -> B,
+        B: OrdSubset,
+        Self: Sized,
+    {
+        self.filter_map(|it| OrdVar::new_checked(f(&it)).map(|key| (it, key)))
+            .min_by(|a, b| a.1.cmp(&b.1))
+            .map(|(it, key)| (it, key.into_inner()))
+    }
+
+    /// Groups the elements by the key returned from `f` into a map sorted by key. Within a group, elements keep their original order.
+    /// Elements whose key is outside the ordered subset as given by `.is_outside_order()` are dropped.
+    ///
+    /// The key function is called exactly once per element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::{OrdSubsetIterExt, OrdVar};
+    ///
+    /// let vec = vec![1.1, 2.5, 1.7, std::f64::NAN, 0.2];
+    /// let groups = vec.into_iter().ord_subset_group_by_key(|num| num.floor());
+    /// assert_eq!(groups.len(), 3);
+    /// assert_eq!(groups[&OrdVar::new(1.0)], [1.1, 1.7]);
+    /// assert_eq!(groups.keys().next(), Some(&OrdVar::new(0.0)));
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_group_by_key<B, F>(self, mut f: F) -> BTreeMap<OrdVar<B>, Vec<Self::Item>>
+    where
+        F: FnMut(&Self::Item) -> B,
+        B: OrdSubset,
+        Self: Sized,
+    {
+        let mut groups = BTreeMap::new();
+        for it in self {
+            if let Some(key) = OrdVar::new_checked(f(&it)) {
+                groups.entry(key).or_insert_with(Vec::new).push(it);
+            }
+        }
+        groups
+    }
+
+    /// Creates an iterator that skips elements whose key is equal to the key of the last yielded element,
+    /// i.e. removes consecutive duplicates by key.
+    /// Keys outside the ordered subset as given by `.is_outside_order()` are never equal to anything,
+    /// so their elements are always yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetIterExt;
+    ///
+    /// let vec = vec![1.1, 1.4, 2.0, 2.2, std::f64::NAN, std::f64::NAN, 2.7];
+    /// let dedup: Vec<_> = vec.into_iter().ord_subset_dedup_by_key(|num| num.floor()).collect();
+    /// assert_eq!(&dedup[..2], &[1.1, 2.0]);
+    /// assert!(dedup[2].is_nan() && dedup[3].is_nan());
+    /// assert_eq!(dedup[4], 2.7);
+    /// ```
+    #[inline]
+    fn ord_subset_dedup_by_key<B, F>(self, f: F) -> DedupByKey<Self, F, B>
+    where
+        F: FnMut(&Self::Item) -> B,
+        B: OrdSubset,
+        Self: Sized,
+    {
+        DedupByKey {
+            iter: self,
+            f,
+            last_key: None,
+        }
+    }
 }
 
 impl<T: ?Sized + Iterator> OrdSubsetIterExt for T {}
+
+/// Returns the maximum element of anything that can be iterated over. Values outside the ordered subset are ignored.
+///
+/// This is a shorthand for `iter.into_iter().ord_subset_max()`.
+///
+/// # Example
+///
+/// ```
+/// let vec = vec![2.0, 3.0, 5.0, std::f64::NAN];
+/// assert_eq!(ord_subset::ord_subset_max(vec), Some(5.0));
+/// ```
+#[inline]
+pub fn ord_subset_max<I>(iter: I) -> Option<I::Item>
+where
+    I: IntoIterator,
+    I::Item: OrdSubset,
+{
+    iter.into_iter().ord_subset_max()
+}
+
+/// Returns the minimum element of anything that can be iterated over. Values outside the ordered subset are ignored.
+///
+/// This is a shorthand for `iter.into_iter().ord_subset_min()`.
+#[inline]
+pub fn ord_subset_min<I>(iter: I) -> Option<I::Item>
+where
+    I: IntoIterator,
+    I::Item: OrdSubset,
+{
+    iter.into_iter().ord_subset_min()
+}
+
+/// An iterator that removes consecutive elements with equal keys.
+///
+/// This struct is created by the `ord_subset_dedup_by_key` method on `OrdSubsetIterExt`.
+#[derive(Clone, Debug)]
+pub struct DedupByKey<I, F, B> {
+    iter: I,
+    f: F,
+    last_key: Option<B>,
+}
+
+impl<I, F, B> Iterator for DedupByKey<I, F, B>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> B,
+    B: OrdSubset,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in &mut self.iter {
+            let key = (self.f)(&item);
+            let is_duplicate = match self.last_key {
+                Some(ref last_key) => {
+                    !key.is_outside_order() && !last_key.is_outside_order() && *last_key == key
+                }
+                None => false,
+            };
+            if !is_duplicate {
+                self.last_key = Some(key);
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        // with nothing yielded yet, at least one element will be
+        let lower = match self.last_key {
+            None if lower > 0 => 1,
+            _ => 0,
+        };
+        (lower, upper)
+    }
+}
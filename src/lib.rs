@@ -43,13 +43,39 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #[cfg(feature = "std")] // attribute not necessary, but rls warns without
 extern crate core;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "rust_decimal")]
+extern crate rust_decimal;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
+mod error;
+mod finite;
 mod iter_ext;
 mod ord_var;
 mod slice_ext;
 mod ord_subset_trait;
+#[cfg(feature = "std")]
+mod vec_ext;
+#[cfg(feature = "std")]
+mod vec_deque_ext;
+#[cfg(feature = "rayon")]
+mod par_iter_ext;
 
+pub use error::SearchError;
+pub use finite::*;
 pub use iter_ext::*;
 pub use ord_var::*;
 pub use slice_ext::*;
 pub use ord_subset_trait::*;
+#[cfg(feature = "std")]
+pub use vec_ext::*;
+#[cfg(feature = "std")]
+pub use vec_deque_ext::*;
+#[cfg(feature = "rayon")]
+pub use par_iter_ext::*;
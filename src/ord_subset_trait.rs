@@ -4,6 +4,31 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use core::cmp::{Ordering, Reverse};
+use core::convert::Infallible;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+#[cfg_attr(rustfmt, rustfmt_skip)]
+use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+                NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize};
+use core::num::{Saturating, Wrapping};
+use core::ops::Deref;
+use core::pin::Pin;
+use core::task::Poll;
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Cow, ToOwned};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+
 /// Trait for types that form a total order when a few values are disallowed.
 ///
 /// `is_outside_order()` must return `true` for these outliers and `false` for anything else.
@@ -33,6 +58,97 @@ where
     }
 }
 
+// Pointer types compare by their pointee.
+// Locks like `Mutex` and `RwLock` aren't `PartialOrd` and having `is_outside_order()` block
+// would be surprising anyway. `RefCell` is skipped for the same reason, it could panic.
+impl<P> OrdSubset for Pin<P>
+where
+    P: Deref,
+    P::Target: OrdSubset,
+{
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        (**self).is_outside_order()
+    }
+}
+
+// Reversing the order doesn't change which values are part of it.
+impl<T: OrdSubset> OrdSubset for Reverse<T> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        self.0.is_outside_order()
+    }
+}
+
+// Integer wrappers that only change the arithmetic compare like the integer.
+impl<T: OrdSubset> OrdSubset for Wrapping<T> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        self.0.is_outside_order()
+    }
+}
+
+impl<T: OrdSubset> OrdSubset for Saturating<T> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        self.0.is_outside_order()
+    }
+}
+
+// `Ok` and `Err` always compare, as `Ok < Err`. Only two values of the same variant can be unordered.
+impl<T: OrdSubset, E: OrdSubset> OrdSubset for Result<T, E> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        match *self {
+            Ok(ref t) => t.is_outside_order(),
+            Err(ref e) => e.is_outside_order(),
+        }
+    }
+}
+
+// `Ready < Pending`, so `Pending` is always ordered.
+impl<T: OrdSubset> OrdSubset for Poll<T> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        match *self {
+            Poll::Ready(ref t) => t.is_outside_order(),
+            Poll::Pending => false,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: ?Sized + OrdSubset> OrdSubset for Box<A> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        (**self).is_outside_order()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: ?Sized + OrdSubset> OrdSubset for Rc<A> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        (**self).is_outside_order()
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<A: ?Sized + OrdSubset> OrdSubset for Arc<A> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        (**self).is_outside_order()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, B: ?Sized + OrdSubset + ToOwned> OrdSubset for Cow<'a, B> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        (**self).is_outside_order()
+    }
+}
+
 #[cfg_attr(feature = "cargo-clippy", allow(float_cmp, eq_op))]
 impl OrdSubset for f64 {
     #[inline(always)]
@@ -51,6 +167,88 @@ impl OrdSubset for f32 {
     }
 }
 
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// The primitive float types `f32` and `f64`. Enables float specific methods like `OrdSubsetSliceExt::ord_subset_sort_floats`.
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait OrdSubsetFloat: OrdSubset + Copy + sealed::Sealed {
+    /// IEEE 754 total order, e.g. `f64::total_cmp`. NaNs with the sign bit set are less than everything else,
+    /// NaNs without it greater. `-0.0` is less than `0.0`.
+    fn total_order_cmp(&self, other: &Self) -> Ordering;
+
+    /// See `f64::is_finite`.
+    fn is_finite(self) -> bool;
+
+    /// See `f64::is_infinite`.
+    fn is_infinite(self) -> bool;
+
+    /// See `f64::is_sign_positive`.
+    fn is_sign_positive(self) -> bool;
+
+    /// See `f64::is_sign_negative`.
+    fn is_sign_negative(self) -> bool;
+
+    /// See `f64::abs`.
+    #[cfg(feature = "std")]
+    fn abs(self) -> Self;
+
+    /// See `f64::sqrt`.
+    #[cfg(feature = "std")]
+    fn sqrt(self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($($T:ident),+) => {
+        $(
+            impl OrdSubsetFloat for $T {
+                #[inline(always)]
+                fn total_order_cmp(&self, other: &Self) -> Ordering {
+                    <$T>::total_cmp(self, other)
+                }
+
+                #[inline(always)]
+                fn is_finite(self) -> bool {
+                    <$T>::is_finite(self)
+                }
+
+                #[inline(always)]
+                fn is_infinite(self) -> bool {
+                    <$T>::is_infinite(self)
+                }
+
+                #[inline(always)]
+                fn is_sign_positive(self) -> bool {
+                    <$T>::is_sign_positive(self)
+                }
+
+                #[inline(always)]
+                fn is_sign_negative(self) -> bool {
+                    <$T>::is_sign_negative(self)
+                }
+
+                #[cfg(feature = "std")]
+                #[inline(always)]
+                fn abs(self) -> Self {
+                    <$T>::abs(self)
+                }
+
+                #[cfg(feature = "std")]
+                #[inline(always)]
+                fn sqrt(self) -> Self {
+                    <$T>::sqrt(self)
+                }
+            }
+        )+
+    }
+}
+
+impl_float!(f32, f64);
+
 trait EnsureOrd: Ord {}
 
 macro_rules! impl_for_ord {
@@ -72,29 +270,28 @@ macro_rules! impl_for_ord {
 }
 
 #[cfg_attr(rustfmt, rustfmt_skip)]
-impl_for_ord!((), u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, bool, char);
+impl_for_ord!((), u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char);
+impl_for_ord!(str);
+impl_for_ord!(IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6);
+// Uninhabited, but allows substituting it for generic `T: OrdSubset`, e.g. in `Result<T, Infallible>`.
+impl_for_ord!(Infallible);
+#[cfg_attr(rustfmt, rustfmt_skip)]
+impl_for_ord!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+              NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize);
+#[cfg(feature = "alloc")]
+impl_for_ord!(String);
 
-macro_rules! array_impls {
-    ($($N:expr),+) => {
-        $(
-			impl<T: OrdSubset> OrdSubset for [T; $N] {
-				#[inline(always)]
-				fn is_outside_order(&self) -> bool {
-					(&self[..]).is_outside_order()
-				}
-			}
-        )+
+#[cfg(feature = "rust_decimal")]
+impl_for_ord!(::rust_decimal::Decimal);
+
+// An empty array has no elements that could be unordered.
+impl<T: OrdSubset, const N: usize> OrdSubset for [T; N] {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        self[..].is_outside_order()
     }
 }
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
-array_impls!(
-	0, 1, 2, 3, 4, 5, 6, 7, 8,
-	9, 10, 11, 12, 13, 14, 15, 16,
-	17, 18, 19, 20, 21, 22, 23, 24,
-	25, 26, 27, 28, 29, 30, 31, 32
-);
-
 impl<T: OrdSubset> OrdSubset for [T] {
     #[inline(always)]
     fn is_outside_order(&self) -> bool {
@@ -102,6 +299,31 @@ impl<T: OrdSubset> OrdSubset for [T] {
     }
 }
 
+// Collections compare lexicographically, just like slices.
+macro_rules! impl_for_collection {
+    ($($type:ident),*) => {
+        $(
+            #[cfg(feature = "alloc")]
+            impl<T: OrdSubset> OrdSubset for $type<T> {
+                #[inline(always)]
+                fn is_outside_order(&self) -> bool {
+                    self.iter().any(OrdSubset::is_outside_order)
+                }
+            }
+        )*
+    };
+}
+
+impl_for_collection!(Vec, VecDeque, LinkedList, BTreeSet);
+
+#[cfg(feature = "alloc")]
+impl<K: OrdSubset, V: OrdSubset> OrdSubset for BTreeMap<K, V> {
+    #[inline(always)]
+    fn is_outside_order(&self) -> bool {
+        self.iter().any(|(k, v)| k.is_outside_order() || v.is_outside_order())
+    }
+}
+
 // code stolen from std library
 macro_rules! tuple_impls {
     ($(
@@ -240,7 +462,7 @@ pub(crate) trait CmpUnwrap: OrdSubset {
     }
 }
 
-impl<T: OrdSubset> CmpUnwrap for T {}
+impl<T: ?Sized + OrdSubset> CmpUnwrap for T {}
 
 // The tests here are primarily compile time tests
 // If the tuple macros were wrong, it would show up in the std library
@@ -254,6 +476,130 @@ mod test {
         assert!(!tup.is_outside_order());
     }
 
+    #[test]
+    fn wide_integers() {
+        assert!(!u128::max_value().is_outside_order());
+        assert!(!i128::min_value().is_outside_order());
+    }
+
+    #[test]
+    fn integer_wrappers() {
+        use core::num::{Saturating, Wrapping};
+        assert!(!Wrapping(1u8).is_outside_order());
+        assert!(!Saturating(-1i64).is_outside_order());
+    }
+
+    #[test]
+    fn reverse() {
+        use core::cmp::Reverse;
+        use core::f64::NAN;
+        assert!(!Reverse(1.0).is_outside_order());
+        assert!(Reverse(NAN).is_outside_order());
+    }
+
+    #[test]
+    fn result() {
+        use core::f64::NAN;
+        assert!(!Ok::<f64, f64>(1.0).is_outside_order());
+        assert!(Ok::<f64, f64>(NAN).is_outside_order());
+        assert!(Err::<f64, f64>(NAN).is_outside_order());
+        assert!(!Err::<f64, u8>(0u8).is_outside_order());
+        assert_eq!(Ok::<f64, f64>(NAN).partial_cmp(&Err(1.0)), Some(core::cmp::Ordering::Less));
+    }
+
+    #[test]
+    fn net_addrs() {
+        use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+        let v4 = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 80);
+        let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 0, 0);
+        assert!(!v4.is_outside_order());
+        assert!(!v6.is_outside_order());
+        assert!(!SocketAddr::from(v4).is_outside_order());
+        assert!(!IpAddr::from(Ipv6Addr::UNSPECIFIED).is_outside_order());
+    }
+
+    #[test]
+    fn poll() {
+        use core::f64::NAN;
+        use core::task::Poll;
+        assert!(!Poll::Ready(1.0).is_outside_order());
+        assert!(Poll::Ready(NAN).is_outside_order());
+        assert!(!Poll::<f64>::Pending.is_outside_order());
+        assert_eq!(Poll::Ready(NAN).partial_cmp(&Poll::Pending), Some(core::cmp::Ordering::Less));
+    }
+
+    #[test]
+    fn infallible() {
+        use core::convert::Infallible;
+        use core::f64::NAN;
+        assert!(!Ok::<f64, Infallible>(1.0).is_outside_order());
+        assert!(Ok::<f64, Infallible>(NAN).is_outside_order());
+    }
+
+    #[test]
+    fn non_zero() {
+        use core::num::{NonZeroI128, NonZeroU64};
+        assert!(!NonZeroU64::new(1).unwrap().is_outside_order());
+        assert!(!NonZeroI128::new(-1).unwrap().is_outside_order());
+    }
+
+    #[test]
+    fn pointers() {
+        use core::f64::NAN;
+        use core::pin::Pin;
+        let (ord, unord) = (1.0, NAN);
+        assert!(!Pin::new(&ord).is_outside_order());
+        assert!(Pin::new(&unord).is_outside_order());
+
+        #[cfg(feature = "alloc")]
+        {
+            use alloc::borrow::Cow;
+            use alloc::boxed::Box;
+            use alloc::rc::Rc;
+            use alloc::string::String;
+            #[cfg(target_has_atomic = "ptr")]
+            use alloc::sync::Arc;
+            use alloc::vec;
+            assert!(!Box::new(ord).is_outside_order());
+            assert!(Box::pin(unord).is_outside_order());
+            assert!(Rc::new(unord).is_outside_order());
+            #[cfg(target_has_atomic = "ptr")]
+            assert!(!Arc::new(ord).is_outside_order());
+            let slice: Box<[f64]> = vec![ord, unord].into_boxed_slice();
+            assert!(slice.is_outside_order());
+            assert!(!Cow::Borrowed(&[ord][..]).is_outside_order());
+            assert!(Cow::<[f64]>::Owned(vec![unord]).is_outside_order());
+            assert!(!String::new().is_outside_order());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn collections() {
+        use alloc::collections::{BTreeMap, LinkedList, VecDeque};
+        use alloc::vec;
+        use alloc::vec::Vec;
+        use core::f64::NAN;
+        assert!(!Vec::<f64>::new().is_outside_order());
+        assert!(vec![0.0, NAN].is_outside_order());
+        assert!(!vec![0.0, 1.0].into_iter().collect::<VecDeque<_>>().is_outside_order());
+        assert!(vec![NAN].into_iter().collect::<LinkedList<_>>().is_outside_order());
+        let mut map = BTreeMap::new();
+        map.insert(1, 0.0);
+        assert!(!map.is_outside_order());
+        map.insert(2, NAN);
+        assert!(map.is_outside_order());
+    }
+
+    #[test]
+    fn arrays() {
+        use core::f64::NAN;
+        assert!(!<[f64; 0]>::default().is_outside_order());
+        assert!([NAN; 3].is_outside_order());
+        assert!([0.0, NAN].is_outside_order());
+        assert!(![0.0f32; 33].is_outside_order());
+    }
+
     #[test]
     #[cfg_attr(rustfmt, rustfmt_skip)]
     fn slice() {
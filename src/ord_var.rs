@@ -4,10 +4,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use core::cmp::Ordering;
-use core::fmt::Debug;
+use core::cmp::{Ordering, Reverse};
+use core::fmt::{self, Debug};
 use ord_subset_trait::*;
-use core::ops::Deref;
+use core::ops::{Deref, Index, Neg};
+use core::str::FromStr;
 
 /// Wrapper to signal that the contained variables have a total order. It's illegal to compare two `OrdVar`s that are not ordered.
 /// For this reason, it's unsafe to create `OrdVar`s without checking. Checked constructors are available for `OrdSubset` types.
@@ -15,7 +16,16 @@ use core::ops::Deref;
 /// # Panics
 ///
 /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b`.
+///
+/// `OrdVar<T>` is guaranteed to have the same memory layout as `T`.
+///
+/// `Hash` is implemented if `T` implements it. `f32` and `f64` don't, because `0.0 == -0.0` but their bits differ,
+/// so neither do `OrdVar<f32>` and `OrdVar<f64>`. Use a `BTreeMap` to key a map by them.
+///
+/// `Borrow<T>` is not implemented either. `BTreeMap::get` requires the borrowed type to be `Ord`, which floats aren't,
+/// so look up a `BTreeMap<OrdVar<f64>, V>` with an `&OrdVar<f64>`.
 #[derive(PartialEq, PartialOrd, Clone, Copy, Debug, Hash)]
+#[repr(transparent)]
 pub struct OrdVar<T: PartialOrd + PartialEq>(T);
 
 impl<T: PartialOrd + PartialEq> OrdVar<T> {
@@ -39,6 +49,7 @@ impl<T: PartialOrd + PartialEq> OrdVar<T> {
     }
 
     /// Constructs an ```Option<OrdVar>``` out of the argument. Returns None if the argument is outside the total order.
+    /// Use `try_new()` if you need the rejected value back.
     #[inline]
     pub fn new_checked(data: T) -> Option<OrdVar<T>>
     where
@@ -50,21 +61,213 @@ impl<T: PartialOrd + PartialEq> OrdVar<T> {
         }
     }
 
-    /// Constructs an `OrdVar` without validity check. Incorrectly constructed `OrdVar`s may panic on calls to `.cmp()`.
-    /// The comparison operators (`>`, `>=`, `=`, `!=`, `<`, `<=`) will not panic but may result in surprising behaviour.
+    /// Constructs a ```Result<OrdVar, T>``` out of the argument. Returns the argument as the error if it is outside the total order.
+    #[inline]
+    pub fn try_new(data: T) -> Result<OrdVar<T>, T>
+    where
+        T: OrdSubset,
+    {
+        match data.is_outside_order() {
+            true => Err(data),
+            false => Ok(OrdVar(data)),
+        }
+    }
+
+    /// Constructs an `OrdVar` out of a value of a totally ordered type. No check is necessary, so this is usable in constants.
+    ///
+    /// ```
+    /// use ord_subset::OrdVar;
+    ///
+    /// const ZERO: OrdVar<u64> = OrdVar::new_const(0);
+    /// assert_eq!(*ZERO, 0);
+    /// ```
     #[inline(always)]
-    pub fn new_unchecked(data: T) -> OrdVar<T> {
+    pub const fn new_const(data: T) -> OrdVar<T>
+    where
+        T: Ord,
+    {
         OrdVar(data)
     }
 
+    /// Constructs an `OrdVar` without validity check.
+    ///
+    /// # Safety
+    ///
+    /// The argument must be inside the total order, i.e. `!data.is_outside_order()`.
+    /// An `OrdVar` violating this breaks the guarantee of its `Ord` impl that other code may rely on.
+    /// It may panic on calls to `.cmp()` and the comparison operators (`>`, `>=`, `=`, `!=`, `<`, `<=`) may result in surprising behaviour.
+    ///
+    /// This is a safety invariant, not only a logic one. All safe ways to create an `OrdVar` check it. Enabling `unchecked_ops` is
+    /// the same promise for the results of operators, and the `bytemuck` feature only allows casting out of `OrdVar<T>`, never into it.
+    #[inline(always)]
+    pub unsafe fn new_unchecked(data: T) -> OrdVar<T> {
+        OrdVar(data)
+    }
+
+    /// Views a slice as a slice of `OrdVar`s without copying. Returns `None` if any element is outside the total order.
+    ///
+    /// This is the checked counterpart to casting `&[T]` to `&[OrdVar<T>]`, which the `bytemuck` feature doesn't allow.
+    ///
+    /// ```
+    /// use ord_subset::OrdVar;
+    ///
+    /// let v = [2.0, 1.0, 3.0];
+    /// let wrapped = OrdVar::wrap_slice(&v).unwrap();
+    /// assert_eq!(wrapped.iter().max(), Some(&OrdVar::new(3.0)));
+    /// assert_eq!(OrdVar::wrap_slice(&[1.0, std::f64::NAN]), None);
+    /// ```
+    #[inline]
+    pub fn wrap_slice(slice: &[T]) -> Option<&[OrdVar<T>]>
+    where
+        T: OrdSubset,
+    {
+        if slice.iter().any(OrdSubset::is_outside_order) {
+            return None;
+        }
+        // `repr(transparent)`, so both slices have the same layout, and every element was checked
+        Some(unsafe { &*(slice as *const [T] as *const [OrdVar<T>]) })
+    }
+
     #[inline(always)]
     pub fn into_inner(self) -> T {
         self.0
     }
+
+    /// Combines two `OrdVar`s into an `OrdVar` of a tuple. Tuples of ordered values are ordered, so no check is necessary.
+    ///
+    /// ```
+    /// use ord_subset::OrdVar;
+    ///
+    /// let pair = OrdVar::new(1.0).zip(OrdVar::new(2.0));
+    /// assert!(pair < OrdVar::new(1.5).zip(OrdVar::new(0.0)));
+    /// ```
+    #[inline(always)]
+    pub fn zip<U: PartialOrd + PartialEq>(self, other: OrdVar<U>) -> OrdVar<(T, U)> {
+        OrdVar((self.0, other.0))
+    }
+
+    /// Wraps the `OrdVar` in `Reverse`, e.g. to turn a `BinaryHeap` into a min-heap.
+    ///
+    /// ```
+    /// use ord_subset::OrdVar;
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let mut heap: BinaryHeap<_> = [3.0, 1.0, 2.0].iter().map(|&x| OrdVar::new(x).reversed()).collect();
+    /// heap.push(OrdVar::new_reversed(0.5));
+    /// assert_eq!(*heap.pop().unwrap().0, 0.5);
+    /// assert_eq!(*heap.pop().unwrap().0, 1.0);
+    /// ```
+    #[inline(always)]
+    pub fn reversed(self) -> Reverse<OrdVar<T>> {
+        Reverse(self)
+    }
+
+    /// Constructs an `OrdVar` out of the argument and wraps it in `Reverse`. See `reversed()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order.
+    #[inline]
+    pub fn new_reversed(data: T) -> Reverse<OrdVar<T>>
+    where
+        T: Debug + OrdSubset,
+    {
+        OrdVar::new(data).reversed()
+    }
+
+    /// Returns `None` if `predicate` returns `false` for the contained value, otherwise returns `Some(self)`.
+    ///
+    /// ```
+    /// use ord_subset::OrdVar;
+    ///
+    /// let positive = |x: f64| OrdVar::new_checked(x).and_then(|x| x.filter(|&x| x > 0.0));
+    /// assert_eq!(positive(1.0), Some(OrdVar::new(1.0)));
+    /// assert_eq!(positive(-1.0), None);
+    /// assert_eq!(positive(std::f64::NAN), None);
+    /// ```
+    #[inline]
+    pub fn filter<P: FnOnce(&T) -> bool>(self, predicate: P) -> Option<OrdVar<T>> {
+        match predicate(&self.0) {
+            true => Some(self),
+            false => None,
+        }
+    }
+}
+
+impl<T: OrdSubsetFloat> OrdVar<T> {
+    /// Compares the contained floats by their IEEE 754 total order (`f64::total_cmp`).
+    ///
+    /// Unlike `cmp`, this never panics, even for `OrdVar`s constructed with `new_unchecked` that contain NaN.
+    ///
+    /// ```
+    /// use ord_subset::OrdVar;
+    /// use std::cmp::Ordering;
+    ///
+    /// let nan = unsafe { OrdVar::new_unchecked(std::f64::NAN) };
+    /// assert_eq!(OrdVar::new(1.0).total_cmp(&nan), Ordering::Less);
+    /// assert_eq!(OrdVar::new(-0.0).total_cmp(&OrdVar::new(0.0)), Ordering::Less);
+    /// ```
+    #[inline]
+    pub fn total_cmp(&self, other: &OrdVar<T>) -> Ordering {
+        self.0.total_order_cmp(&other.0)
+    }
+
+    /// Returns `true` if the value is not infinite. An `OrdVar` holding a float is never NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        self.0.is_finite()
+    }
+
+    /// Returns `true` if the value is positive or negative infinity.
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.0.is_infinite()
+    }
+
+    /// Returns `true` if the value is positive, including `+0.0` and positive infinity.
+    #[inline]
+    pub fn is_sign_positive(&self) -> bool {
+        self.0.is_sign_positive()
+    }
+
+    /// Returns `true` if the value is negative, including `-0.0` and negative infinity.
+    #[inline]
+    pub fn is_sign_negative(&self) -> bool {
+        self.0.is_sign_negative()
+    }
+
+    /// Returns the absolute value. It is always inside the total order.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn abs(self) -> OrdVar<T> {
+        OrdVar(self.0.abs())
+    }
+
+    /// Returns the square root or `None` for negative values, where it would be NaN.
+    /// The square root of `-0.0` is `-0.0`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn sqrt(self) -> Option<OrdVar<T>> {
+        OrdVar::new_checked(self.0.sqrt())
+    }
 }
 
 impl<T: PartialOrd + PartialEq> Eq for OrdVar<T> {}
 
+impl<T: PartialOrd + PartialEq> PartialEq<T> for OrdVar<T> {
+    #[inline(always)]
+    fn eq(&self, other: &T) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<T: PartialOrd + PartialEq> PartialOrd<T> for OrdVar<T> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
 impl<T: PartialOrd + PartialEq> Ord for OrdVar<T> {
     #[inline]
     fn cmp(&self, other: &Self) -> Ordering {
@@ -89,6 +292,41 @@ impl<T: PartialOrd + PartialEq> AsRef<T> for OrdVar<T> {
     }
 }
 
+/// Indexes into the contained value. `IndexMut` is not implemented, as it could move the value out of the total order.
+impl<T: PartialOrd + PartialEq + Index<Idx>, Idx> Index<Idx> for OrdVar<T> {
+    type Output = T::Output;
+
+    #[inline]
+    fn index(&self, idx: Idx) -> &Self::Output {
+        &self.0[idx]
+    }
+}
+
+/// Values of `Ord` types are always inside the total order, so the conversion can't fail.
+/// Types that are only `PartialOrd`, like the floats, must go through `OrdVar::new()` or `OrdVar::try_new()` instead.
+impl<T: Ord> From<T> for OrdVar<T> {
+    #[inline(always)]
+    fn from(data: T) -> Self {
+        OrdVar::new_const(data)
+    }
+}
+
+/// Iterates over the contained value.
+impl<T: PartialOrd + PartialEq + IntoIterator> IntoIterator for OrdVar<T> {
+    type Item = T::Item;
+    type IntoIter = T::IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Wraps `T::default()`.
+///
+/// # Panics
+///
+/// Panics if the default value of `T` is outside of the total order.
 impl<T: Default + OrdSubset + Debug> Default for OrdVar<T> {
     #[inline(always)]
     fn default() -> Self {
@@ -96,23 +334,134 @@ impl<T: Default + OrdSubset + Debug> Default for OrdVar<T> {
     }
 }
 
+impl<T: PartialOrd + PartialEq + OrdSubset + FromStr> FromStr for OrdVar<T> {
+    type Err = ParseOrdVarError<T::Err>;
+
+    /// Parses a `T` and wraps it. Fails if `T` can't be parsed or if the parsed value is outside the total order.
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = s.parse().map_err(ParseOrdVarError::Parse)?;
+        OrdVar::try_new(data).map_err(|_| ParseOrdVarError::OutsideOrder)
+    }
+}
+
+/// An error which can be returned when parsing an `OrdVar<T>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseOrdVarError<E> {
+    /// The string could not be parsed into a `T`.
+    Parse(E),
+    /// The parsed value is outside the total order.
+    OutsideOrder,
+}
+
+impl<E: fmt::Display> fmt::Display for ParseOrdVarError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseOrdVarError::Parse(ref err) => err.fmt(f),
+            ParseOrdVarError::OutsideOrder => f.write_str("parsed value is outside of total order"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: ::std::error::Error> ::std::error::Error for ParseOrdVarError<E> {}
+
+// Formatting is forwarded to the contained value
+macro_rules! fmt_impls {
+    ($($Trait:ident),+) => {
+        $(
+            impl<T: PartialOrd + PartialEq + fmt::$Trait> fmt::$Trait for OrdVar<T> {
+                #[inline]
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::$Trait::fmt(&self.0, f)
+                }
+            }
+        )+
+    }
+}
+
+fmt_impls!(Display, LowerExp, UpperExp, Binary, Octal, LowerHex, UpperHex, Pointer);
+
+// Wraps the result of an operation. Checked unless feature `unchecked_ops` is active.
+#[inline(always)]
+fn construct<T: OrdSubset + Debug>(t: T) -> OrdVar<T> {
+    match cfg!(feature = "unchecked_ops") {
+        // SAFETY: enabling `unchecked_ops` is the user's promise that results of operations stay inside the total order
+        true => unsafe { OrdVar::new_unchecked(t) },
+        false => OrdVar::new(t),
+    }
+}
+
+/// Negation is available without feature `ops`. The result is checked like that of the other ops.
+impl<T> Neg for OrdVar<T>
+where
+    T: PartialOrd + PartialEq + Neg,
+    T::Output: PartialOrd + PartialEq + Debug + OrdSubset,
+{
+    type Output = OrdVar<T::Output>;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        construct(self.into_inner().neg())
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls {
+    //! Only casts out of `OrdVar<T>` are provided. Casting a `T` into an `OrdVar<T>` would
+    //! skip the check of the constructors, see `OrdVar::new_unchecked()`.
+    use bytemuck::{NoUninit, Zeroable};
+    use super::OrdVar;
+
+    // `repr(transparent)`, so the bytes are exactly those of `T`
+    unsafe impl<T: PartialOrd + PartialEq + NoUninit> NoUninit for OrdVar<T> {}
+
+    // All-zero bytes are inside the total order for the primitive numbers,
+    // but not necessarily for other `T: Zeroable`.
+    macro_rules! zeroable_impls {
+        ($($T:ty),+) => {
+            $(
+                unsafe impl Zeroable for OrdVar<$T> {}
+            )+
+        }
+    }
+
+    zeroable_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use ord_subset_trait::*;
+    use super::OrdVar;
+
+    impl<T: PartialOrd + PartialEq + Serialize> Serialize for OrdVar<T> {
+        #[inline]
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    /// Fails if the deserialized value is outside the total order.
+    impl<'de, T: PartialOrd + PartialEq + OrdSubset + Deserialize<'de>> Deserialize<'de> for OrdVar<T> {
+        #[inline]
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let data = T::deserialize(deserializer)?;
+            OrdVar::try_new(data).map_err(|_| D::Error::custom("cannot deserialize value outside of total order as OrdVar"))
+        }
+    }
+}
+
 #[cfg(feature = "ops")]
 mod ops {
     // would love to be able to macro these away somehow
     #[cfg_attr(rustfmt, rustfmt_skip)]
-	use core::ops::{Add, Sub, Mul, Div, Rem, BitAnd, BitOr, BitXor, Shl, Shr, Neg, Not,
+	use core::ops::{Add, Sub, Mul, Div, Rem, BitAnd, BitOr, BitXor, Shl, Shr, Not,
                 AddAssign, SubAssign, MulAssign, DivAssign, RemAssign, BitAndAssign, BitOrAssign, BitXorAssign, ShlAssign, ShrAssign,};
     use core::fmt::Debug;
+    use core::iter::{Product, Sum};
     use ord_subset_trait::*;
-    use super::OrdVar;
-
-    #[inline(always)]
-    fn construct<T: OrdSubset + Debug>(t: T) -> OrdVar<T> {
-        match cfg!(feature = "unchecked_ops") {
-            true => OrdVar::new_unchecked(t),
-            false => OrdVar::new(t),
-        }
-    }
+    use super::{construct, OrdVar};
 
     // -----------------  binary ops -----------------------------------------------
 
@@ -389,25 +738,91 @@ mod ops {
     }
 
     // ------------------------ unary ops ------------------------------------------
-    impl<T> Neg for OrdVar<T>
+    impl<T> Not for OrdVar<T>
     where
-        T: PartialOrd + PartialEq + Neg,
+        T: PartialOrd + PartialEq + Not,
         T::Output: PartialOrd + PartialEq + Debug + OrdSubset,
     {
         type Output = OrdVar<T::Output>;
-        fn neg(self) -> Self::Output {
-            construct(self.into_inner().neg())
+        fn not(self) -> Self::Output {
+            construct(self.into_inner().not())
         }
     }
 
-    impl<T> Not for OrdVar<T>
+    // ------------------ ops with OrdVar on the right-hand side -------------------
+    // A generic impl<T, RHS> Add<OrdVar<RHS>> for OrdVar<T> would overlap with
+    // the impls above, so these exist only for the primitive types.
+    // They unwrap the rhs and delegate to the impls above.
+    macro_rules! ord_var_rhs_ops {
+        ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident; $($T:ty),*) => {
+            $(
+                impl $Op<OrdVar<$T>> for OrdVar<$T> {
+                    type Output = OrdVar<$T>;
+                    fn $op(self, rhs: OrdVar<$T>) -> Self::Output {
+                        <Self as $Op<$T>>::$op(self, rhs.0)
+                    }
+                }
+
+                impl $OpAssign<OrdVar<$T>> for OrdVar<$T> {
+                    fn $op_assign(&mut self, rhs: OrdVar<$T>) {
+                        <Self as $OpAssign<$T>>::$op_assign(self, rhs.0)
+                    }
+                }
+            )*
+        }
+    }
+
+    macro_rules! ord_var_rhs_ops_for_primitives {
+        ($($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident);*) => {
+            $(
+                ord_var_rhs_ops!($Op, $op, $OpAssign, $op_assign; f32, f64, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+            )*
+        }
+    }
+
+    ord_var_rhs_ops_for_primitives!(
+        Add, add, AddAssign, add_assign;
+        Sub, sub, SubAssign, sub_assign;
+        Mul, mul, MulAssign, mul_assign;
+        Div, div, DivAssign, div_assign;
+        Rem, rem, RemAssign, rem_assign
+    );
+
+    // ------------------------ iterator folds -------------------------------------
+    // The result is checked once at the end, like the result of any other op
+    impl<T> Sum for OrdVar<T>
     where
-        T: PartialOrd + PartialEq + Not,
-        T::Output: PartialOrd + PartialEq + Debug + OrdSubset,
+        T: PartialOrd + PartialEq + Debug + OrdSubset + Sum,
     {
-        type Output = OrdVar<T::Output>;
-        fn not(self) -> Self::Output {
-            construct(self.into_inner().not())
+        fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+            construct(iter.map(OrdVar::into_inner).sum())
+        }
+    }
+
+    impl<'a, T> Sum<&'a OrdVar<T>> for OrdVar<T>
+    where
+        T: PartialOrd + PartialEq + Debug + OrdSubset + Sum<&'a T> + 'a,
+    {
+        fn sum<I: Iterator<Item = &'a OrdVar<T>>>(iter: I) -> Self {
+            construct(iter.map(|ord_var| &ord_var.0).sum())
+        }
+    }
+
+    impl<T> Product for OrdVar<T>
+    where
+        T: PartialOrd + PartialEq + Debug + OrdSubset + Product,
+    {
+        fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+            construct(iter.map(OrdVar::into_inner).product())
+        }
+    }
+
+    impl<'a, T> Product<&'a OrdVar<T>> for OrdVar<T>
+    where
+        T: PartialOrd + PartialEq + Debug + OrdSubset + Product<&'a T> + 'a,
+    {
+        fn product<I: Iterator<Item = &'a OrdVar<T>>>(iter: I) -> Self {
+            construct(iter.map(|ord_var| &ord_var.0).product())
         }
     }
 }
@@ -0,0 +1,94 @@
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0 or the MIT license
+// http://opensource.org/licenses/MIT, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+use ord_subset_trait::*;
+use ord_var::*;
+use rayon::iter::ParallelIterator;
+
+/// Parallel versions of the reductions of `OrdSubsetIterExt` for rayon's `ParallelIterator`.
+///
+/// Values outside the ordered subset are dropped before the partial results of the threads are combined.
+/// If the extremum is unique, the result is the same as the sequential one.
+/// If multiple elements are equally maximum or minimum, it is unspecified which of them is returned.
+pub trait OrdSubsetParIterExt: ParallelIterator {
+    /// Consumes the entire iterator to return the maximum element.
+    /// Values outside the ordered subset as given by `.is_outside_order()` are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate ord_subset;
+    /// extern crate rayon;
+    /// use ord_subset::OrdSubsetParIterExt;
+    /// use rayon::prelude::*;
+    ///
+    /// fn main() {
+    ///     let vec = vec![2.0, 3.0, 5.0, std::f64::NAN];
+    ///     let max = vec.par_iter().ord_subset_max().unwrap();
+    ///     assert_eq!(&5.0, max);
+    /// }
+    /// ```
+    #[inline]
+    fn ord_subset_max(self) -> Option<Self::Item>
+    where
+        Self::Item: OrdSubset,
+    {
+        self.filter_map(OrdVar::new_checked)
+            .reduce_with(cmp::max)
+            .map(OrdVar::into_inner)
+    }
+
+    /// Consumes the entire iterator to return the minimum element.
+    /// Values outside the ordered subset as given by `.is_outside_order()` are ignored.
+    #[inline]
+    fn ord_subset_min(self) -> Option<Self::Item>
+    where
+        Self::Item: OrdSubset,
+    {
+        self.filter_map(OrdVar::new_checked)
+            .reduce_with(cmp::min)
+            .map(OrdVar::into_inner)
+    }
+
+    /// Returns the element that gives the maximum value from the specified function.
+    /// Values outside the ordered subset as given by `.is_outside_order()` on the mapped value are ignored.
+    ///
+    /// The key function is called exactly once per element.
+    #[inline]
+    fn ord_subset_max_by_key<F, B>(self, f: F) -> Option<Self::Item>
+    where
+        F: Fn(&Self::Item) -> B + Sync + Send,
+        B: OrdSubset + Send,
+    {
+        self.filter_map(|x| OrdVar::new_checked(f(&x)).map(|key| (key, x)))
+            .reduce_with(|a, b| match b.0 >= a.0 {
+                true => b,
+                false => a,
+            })
+            .map(|(_, x)| x)
+    }
+
+    /// Returns the element that gives the minimum value from the specified function.
+    /// Values outside the ordered subset as given by `.is_outside_order()` on the mapped value are ignored.
+    ///
+    /// The key function is called exactly once per element.
+    #[inline]
+    fn ord_subset_min_by_key<F, B>(self, f: F) -> Option<Self::Item>
+    where
+        F: Fn(&Self::Item) -> B + Sync + Send,
+        B: OrdSubset + Send,
+    {
+        self.filter_map(|x| OrdVar::new_checked(f(&x)).map(|key| (key, x)))
+            .reduce_with(|a, b| match b.0 < a.0 {
+                true => b,
+                false => a,
+            })
+            .map(|(_, x)| x)
+    }
+}
+
+impl<T: ParallelIterator> OrdSubsetParIterExt for T {}
@@ -4,12 +4,20 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use error::*;
 use ord_subset_trait::*;
+use ord_var::*;
+use core::borrow::Borrow;
 use core::cmp::Ordering::{self, Equal, Greater, Less};
+use core::mem;
+use core::ops::Range;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
 
-static ERROR_BINARY_SEARCH_OUTSIDE_ORDER: &str =
-    "Attempted binary search for value outside total order";
-static ERROR_BINARY_SEARCH_EXPECT: &str = "Unexpected None for a.partial_cmp(b), a,b inside order. Violated OrdSubset contract or attempted binary search on unsorted data";
 
 // Wrapper for comparison functions
 // Treats unordered values as greater than any ordered
@@ -30,6 +38,407 @@ where
     }
 }
 
+/// Total order used by the sorts of `OrdSubsetSliceExt`. Values outside the ordered subset are greater than all values inside it
+/// and equal to each other.
+///
+/// This can be passed to std functions expecting a comparator, e.g. `.sort_by(ord_subset_cmp)`.
+///
+/// # Example
+///
+/// ```
+/// use ord_subset::ord_subset_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(ord_subset_cmp(&std::f64::NAN, &1.0), Ordering::Greater);
+///
+/// let mut v = vec![std::f64::NAN, 3.0, 1.0];
+/// v.sort_by(ord_subset_cmp);
+/// assert_eq!(&v[..2], &[1.0, 3.0]);
+/// ```
+///
+/// # Panics
+///
+/// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+#[inline]
+pub fn ord_subset_cmp<T: OrdSubset>(a: &T, b: &T) -> Ordering {
+    cmp_unordered_greater_all(a, b, |a, b| a.cmp_unwrap(b))
+}
+
+/// Same as `ord_subset_cmp` but returns `None` instead of panicking for two values inside the total order that can't be compared.
+#[inline]
+pub fn ord_subset_partial_cmp<T: OrdSubset>(a: &T, b: &T) -> Option<Ordering> {
+    match (a.is_outside_order(), b.is_outside_order()) {
+        (false, false) => a.partial_cmp(b),
+        _ => Some(cmp_unordered_greater_all(a, b, |_, _| Equal)),
+    }
+}
+
+/// Placement of values outside the total order by the `ord_subset_sort*_with` methods of `OrdSubsetSliceExt`.
+///
+/// The binary searches only support the `Back` layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnorderedPolicy {
+    /// Put values outside the total order after all ordered values. This is what the regular sorts do.
+    Back,
+    /// Put values outside the total order before all ordered values.
+    Front,
+}
+
+impl Default for UnorderedPolicy {
+    #[inline]
+    fn default() -> Self {
+        UnorderedPolicy::Back
+    }
+}
+
+// Binary search in the first `n_ordered` elements with a comparison that may fail.
+// A failed comparison ends the search early by pretending to have found a match.
+#[inline]
+fn try_binary_search_in_prefix<T, F>(
+    slice: &[T],
+    n_ordered: usize,
+    mut f: F,
+) -> Result<Result<usize, usize>, SearchError>
+where
+    F: FnMut(&T) -> Option<Ordering>,
+{
+    let mut violated = false;
+    let result = slice[..n_ordered].binary_search_by(|x| {
+        f(x).unwrap_or_else(|| {
+            violated = true;
+            Equal
+        })
+    });
+    match violated {
+        true => Err(SearchError::ContractViolation),
+        false => Ok(result),
+    }
+}
+
+// Zero-based index of the nearest rank `ceil(pos)` for a non-negative `pos` = quantile * number of values.
+// The product can exceed an integer by a few ulps, e.g. 0.55 * 100.0 = 55.00000000000001,
+// so that error is subtracted first or the rank would come out one too high.
+// No float rounding functions in core.
+#[inline]
+fn nearest_rank_index(pos: f64) -> usize {
+    let pos = pos - pos * (4.0 * f64::EPSILON);
+    let mut rank = pos as usize;
+    if (rank as f64) < pos {
+        rank += 1;
+    }
+    rank.saturating_sub(1)
+}
+
+// Index of the first element for which `pred` is false
+// in a slice where all elements satisfying `pred` come first
+#[inline]
+pub(crate) fn partition_point<T, F>(slice: &[T], mut pred: F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    // never returns Equal, so the result is always the insertion point
+    match slice.binary_search_by(|x| match pred(x) {
+        true => Less,
+        false => Greater,
+    }) {
+        Ok(idx) | Err(idx) => idx,
+    }
+}
+
+// Stable partition, elements satisfying `pred` come first.
+// Returns the number of those elements.
+// Collects the new order of the indices and applies it as a permutation. O(n) time and extra space.
+#[cfg(feature = "alloc")]
+fn stable_partition<T, F>(slice: &mut [T], pred: &mut F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut perm = Vec::with_capacity(slice.len());
+    let mut rejected = Vec::new();
+    for (i, x) in slice.iter().enumerate() {
+        match pred(x) {
+            true => perm.push(i),
+            false => rejected.push(i),
+        }
+    }
+    let n_true = perm.len();
+    perm.extend(rejected);
+    apply_permutation(slice, &perm);
+    n_true
+}
+
+// Stable in-place partition, elements satisfying `pred` come first.
+// Returns the number of those elements.
+// Partitions both halves, then rotates the middle. O(n log n) swaps, no allocation.
+#[cfg(not(feature = "alloc"))]
+fn stable_partition<T, F>(slice: &mut [T], pred: &mut F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    match slice.len() {
+        0 => 0,
+        1 => pred(&slice[0]) as usize,
+        len => {
+            let mid = len / 2;
+            let n_left = stable_partition(&mut slice[..mid], pred);
+            let n_right = stable_partition(&mut slice[mid..], pred);
+            // [left true | left false | right true | right false]
+            slice[n_left..mid + n_right].rotate_left(mid - n_left);
+            n_left + n_right
+        }
+    }
+}
+
+// Reorders `slice` so that `slice[i]` becomes the element previously at `perm[i]`, following the cycles of `perm`.
+#[cfg(feature = "alloc")]
+fn apply_permutation<T>(slice: &mut [T], perm: &[usize]) {
+    assert_eq!(slice.len(), perm.len(), "permutation must have the same length as the slice");
+    let mut visited = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        if visited[start] {
+            continue;
+        }
+        // Walk the cycle starting at `start`. After each swap, position `i` holds its final element
+        // and position `next` holds the element that was at `start`.
+        let mut i = start;
+        loop {
+            visited[i] = true;
+            let next = perm[i];
+            if next == start {
+                break;
+            }
+            assert!(!visited[next], "not a permutation");
+            slice.swap(i, next);
+            i = next;
+        }
+    }
+}
+
+// Unstable in-place partition in a single pass, elements satisfying `pred` come first and keep their order.
+// Returns the number of those elements.
+fn swap_partition<T, F>(slice: &mut [T], pred: &mut F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut n_true = 0;
+    for i in 0..slice.len() {
+        if pred(&slice[i]) {
+            slice.swap(n_true, i);
+            n_true += 1;
+        }
+    }
+    n_true
+}
+
+// Moves all elements for which `is_ordered` returns false to the back or the front, depending on `policy`,
+// and returns the range of the others. The sorts only need to sort that range afterwards
+// and never have to compare against values outside the total order.
+// `stable` keeps the relative order of both groups, otherwise only that of the ordered values
+// if they go first.
+fn partition_ordered<T, F>(
+    slice: &mut [T],
+    policy: UnorderedPolicy,
+    stable: bool,
+    mut is_ordered: F,
+) -> Range<usize>
+where
+    F: FnMut(&T) -> bool,
+{
+    let len = slice.len();
+    let mut goes_first = |x: &T| match policy {
+        UnorderedPolicy::Back => is_ordered(x),
+        UnorderedPolicy::Front => !is_ordered(x),
+    };
+    // elements before the first misplaced one are already partitioned
+    // this makes the partition a single cheap pass for slices without unordered values
+    let start = slice.iter().position(|x| !goes_first(x)).unwrap_or(len);
+    let rest = &mut slice[start..];
+    let n_first = start + match stable {
+        true => stable_partition(rest, &mut goes_first),
+        false => swap_partition(rest, &mut goes_first),
+    };
+    match policy {
+        UnorderedPolicy::Back => 0..n_first,
+        UnorderedPolicy::Front => n_first..len,
+    }
+}
+
+// Single pass search for the minimum and maximum by key. All keys must be inside the total order.
+// Returns the items of the first minimum and the last maximum, like the iterator methods.
+// Keys are never cloned, each is either stored as the new min or max or dropped.
+#[inline]
+fn min_max_by_key<I, K, It>(mut iter: It) -> Option<(I, I)>
+where
+    It: Iterator<Item = (I, K)>,
+    I: Copy,
+    K: OrdSubset,
+{
+    let mut min = iter.next()?;
+    // None while the maximum is the minimum
+    let mut max: Option<(I, K)> = None;
+    for (item, key) in iter {
+        if key.cmp_unwrap(&min.1) == Less {
+            let old_min = mem::replace(&mut min, (item, key));
+            if max.is_none() {
+                max = Some(old_min);
+            }
+        } else {
+            let is_new_max = match max {
+                Some((_, ref max_key)) => key.cmp_unwrap(max_key) != Less,
+                None => true,
+            };
+            if is_new_max {
+                max = Some((item, key));
+            }
+        }
+    }
+    let max_item = max.map_or(min.0, |max| max.0);
+    Some((min.0, max_item))
+}
+
+// Appends the merge of two sorted slices to `out`. `left_len` and `right_len` are the lengths of the ordered prefixes.
+// Ties are taken from `left` first. The unordered tails are appended after all ordered values, `left`'s first.
+#[cfg(feature = "std")]
+fn merge_into<T, F>(left: &[T], left_len: usize, right: &[T], right_len: usize, mut compare: F, out: &mut Vec<T>)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    out.reserve(left.len() + right.len());
+
+    let (mut i, mut j) = (0, 0);
+    while i < left_len && j < right_len {
+        match compare(&right[j], &left[i]) {
+            Less => {
+                out.push(right[j].clone());
+                j += 1;
+            }
+            _ => {
+                out.push(left[i].clone());
+                i += 1;
+            }
+        }
+    }
+    out.extend_from_slice(&left[i..left_len]);
+    out.extend_from_slice(&right[j..right_len]);
+    out.extend_from_slice(&left[left_len..]);
+    out.extend_from_slice(&right[right_len..]);
+}
+
+// Extremum of every window of width `w` with a monotonic deque of indices. O(n).
+// An index is dropped from the back once a new element makes it irrelevant: `dominates(new, old)`.
+#[cfg(feature = "std")]
+fn sliding_extremum<T, F>(slice: &[T], w: usize, mut dominates: F) -> Vec<Option<&T>>
+where
+    T: OrdSubset,
+    F: FnMut(&T, &T) -> bool,
+{
+    assert!(w != 0, "window size must be non-zero");
+    let n_windows = (slice.len() + 1).saturating_sub(w);
+    let mut extrema = Vec::with_capacity(n_windows);
+    let mut candidates: VecDeque<usize> = VecDeque::new();
+    for (idx, x) in slice.iter().enumerate() {
+        if !x.is_outside_order() {
+            while let Some(&back) = candidates.back() {
+                if !dominates(x, &slice[back]) {
+                    break;
+                }
+                candidates.pop_back();
+            }
+            candidates.push_back(idx);
+        }
+        if idx + 1 < w {
+            continue;
+        }
+        let window_start = idx + 1 - w;
+        // at most one index can leave the window per step
+        if window_start > 0 && candidates.front() == Some(&(window_start - 1)) {
+            candidates.pop_front();
+        }
+        extrema.push(candidates.front().map(|&front| &slice[front]));
+    }
+    extrema
+}
+
+// All elements comparing equal to the extremum, in slice order.
+// `better` is the ordering of a new extremum relative to the current one.
+#[cfg(feature = "std")]
+fn extremum_set<T: OrdSubset>(slice: &[T], better: Ordering) -> Vec<&T> {
+    let mut set: Vec<&T> = Vec::new();
+    for x in slice.iter().filter(|x| !x.is_outside_order()) {
+        let ord = match set.first() {
+            Some(extremum) => x.cmp_unwrap(extremum),
+            None => better,
+        };
+        if ord == better {
+            set.clear();
+            set.push(x);
+        } else if ord == Equal {
+            set.push(x);
+        }
+    }
+    set
+}
+
+// Restores the max-heap property for the subtree at `node` in `keys[..end]`.
+// Every swap is mirrored in `values`.
+fn sift_down<B: OrdSubset, T>(keys: &mut [B], values: &mut [T], mut node: usize, end: usize) {
+    loop {
+        let mut child = 2 * node + 1;
+        if child >= end {
+            break;
+        }
+        if child + 1 < end && keys[child].cmp_unwrap(&keys[child + 1]) == Less {
+            child += 1;
+        }
+        if keys[node].cmp_unwrap(&keys[child]) != Less {
+            break;
+        }
+        keys.swap(node, child);
+        values.swap(node, child);
+        node = child;
+    }
+}
+
+// Heapsort of `keys`, moving `values` along. All keys must be inside the total order.
+// In place and without allocation, so it works without std.
+fn heapsort_by_keys<B: OrdSubset, T>(keys: &mut [B], values: &mut [T]) {
+    let len = keys.len();
+    for node in (0..len / 2).rev() {
+        sift_down(keys, values, node, len);
+    }
+    for end in (1..len).rev() {
+        keys.swap(0, end);
+        values.swap(0, end);
+        sift_down(keys, values, 0, end);
+    }
+}
+
+// Binary search for the first or last element for which `cmp` returns `Equal`.
+// `cmp` compares an element to the target and must return `Greater` for unordered elements.
+#[inline]
+fn binary_search_edge<T, F>(slice: &[T], mut cmp: F, last: bool) -> Result<usize, usize>
+where
+    F: FnMut(&T) -> Ordering,
+{
+    match last {
+        false => {
+            let idx = partition_point(slice, |e| cmp(e) == Less);
+            match slice.get(idx) {
+                Some(e) if cmp(e) == Equal => Ok(idx),
+                _ => Err(idx),
+            }
+        }
+        true => {
+            let idx = partition_point(slice, |e| cmp(e) != Greater);
+            match idx.checked_sub(1) {
+                Some(prev) if cmp(&slice[prev]) == Equal => Ok(prev),
+                _ => Err(idx),
+            }
+        }
+    }
+}
+
 pub trait OrdSubsetSliceExt<T> {
     /// Sort the slice. Values outside the ordered subset are put at the end in their original order.
     ///
@@ -74,6 +483,19 @@ pub trait OrdSubsetSliceExt<T> {
         T: OrdSubset,
         F: FnMut(&T, &T) -> Ordering;
 
+    /// Sorts the slice in reverse order, using `compare` to order elements. `compare` describes the ascending order,
+    /// its arguments are swapped internally. Values outside the total order are put at the end in their original order (i.e. not reversed).
+    /// `compare` will not be called on them.
+    ///
+    /// This delegates to `.sort_by()` in the std library. See [official docs](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by) for
+    /// time and space complexity of the current implementation.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_rev_by<F>(&mut self, compare: F)
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering;
+
     /// Sorts the slice, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
     /// the total order will be put at the end in their original order.
     ///
@@ -86,6 +508,174 @@ pub trait OrdSubsetSliceExt<T> {
         B: OrdSubset,
         F: FnMut(&T) -> B;
 
+    /// Like `ord_subset_sort_by_key`, but the key function returns a reference into the element.
+    /// This allows sorting by keys like a `String` field without cloning them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut v = vec![("b".to_string(), 1), ("a".to_string(), 2)];
+    /// v.ord_subset_sort_by_key_ref(|x| &x.0);
+    /// assert_eq!(v[0].1, 2);
+    /// ```
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_by_key_ref<B, F>(&mut self, f: F)
+    where
+        Self: AsMut<[T]>,
+        B: ?Sized + OrdSubset,
+        F: for<'a> FnMut(&'a T) -> &'a B;
+
+    /// Sorts the slice in reverse order, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
+    /// the total order will be put at the end in their original order (i.e. not reversed).
+    ///
+    /// This delegates to `.sort_by()` in the std library. See [official docs](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by) for
+    /// time and space complexity of the current implementation.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_rev_by_key<B, F>(&mut self, f: F)
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns a sorted copy of the slice, leaving the slice itself untouched. Values outside the ordered subset are put at the end
+    /// in their original order, like `ord_subset_sort`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [3.0, std::f64::NAN, 1.0, 2.0];
+    /// let sorted = s.to_ord_subset_sorted_vec();
+    /// assert_eq!(sorted[..3], [1.0, 2.0, 3.0]);
+    /// assert_eq!(s[0], 3.0);
+    /// ```
+    #[cfg(feature = "std")]
+    fn to_ord_subset_sorted_vec(&self) -> Vec<T>
+    where
+        T: OrdSubset + Clone;
+
+    /// Returns a copy of the slice sorted in reverse order. Values outside the ordered subset are put at the end
+    /// in their original order, like `ord_subset_sort_rev`.
+    #[cfg(feature = "std")]
+    fn to_ord_subset_sorted_vec_rev(&self) -> Vec<T>
+    where
+        T: OrdSubset + Clone;
+
+    /// Returns a copy of the slice sorted by the key returned from `f`. Entries whose key is outside the ordered subset
+    /// are put at the end in their original order, like `ord_subset_sort_by_key`.
+    #[cfg(feature = "std")]
+    fn to_ord_subset_sorted_vec_by_key<B, F>(&self, f: F) -> Vec<T>
+    where
+        T: Clone,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the permutation of indices that would sort the slice, without modifying it.
+    /// Indices of values outside the total order are put at the end. The sort is stable, equal and unordered
+    /// values keep their original relative order.
+    ///
+    /// Use [`ord_subset_apply_permutation`](#tymethod.ord_subset_apply_permutation) to reorder this or any other slice of the same length accordingly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let keys = [3.0, std::f64::NAN, 1.0, 2.0];
+    /// let mut names = ["c", "nan", "a", "b"];
+    /// let perm = keys.ord_subset_argsort();
+    /// assert_eq!(perm, [2, 3, 0, 1]);
+    ///
+    /// names.ord_subset_apply_permutation(&perm);
+    /// assert_eq!(names, ["a", "b", "c", "nan"]);
+    /// ```
+    #[cfg(feature = "std")]
+    fn ord_subset_argsort(&self) -> Vec<usize>
+    where
+        T: OrdSubset;
+
+    /// Like `ord_subset_argsort`, but uses `key` to extract a key by which to order the sort by.
+    #[cfg(feature = "std")]
+    fn ord_subset_argsort_by_key<B, F>(&self, f: F) -> Vec<usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Sorts the slice and reorders `values` the same way, so that each value stays paired with its key.
+    /// Keys outside the total order are put at the end. The sort is stable.
+    ///
+    /// This computes `ord_subset_argsort` and applies the permutation to both slices instead of
+    /// building a temporary `Vec<(K, V)>`. The extra memory is O(n) `usize`s and `bool`s for the
+    /// permutation, the merge buffer of its stable sort and the visited flags of each application.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut xs = [3.0, std::f64::NAN, 1.0, 2.0];
+    /// let mut ys = [30.0, -1.0, 10.0, 20.0];
+    /// xs.ord_subset_sort_together(&mut ys);
+    /// assert_eq!(ys, [10.0, 20.0, 30.0, -1.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` has a different length than the slice.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_together<V>(&mut self, values: &mut [V])
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Reorders the slice so that the element at index `i` is the one previously at `perm[i]`.
+    /// Applying the result of `ord_subset_argsort` sorts the slice the same way.
+    ///
+    /// Runs in O(n) by following the cycles of the permutation in place. It allocates a bitmap of visited indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `perm` has a different length than the slice or is not a permutation of `0..len`.
+    #[cfg(feature = "std")]
+    fn ord_subset_apply_permutation(&mut self, perm: &[usize])
+    where
+        Self: AsMut<[T]>;
+
+    /// Sort the slice. Values outside the ordered subset are put at the front or the back according to `policy`, in their original order.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_with(&mut self, policy: UnorderedPolicy)
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Sort the slice in reverse order. Values outside the ordered subset are put at the front or the back according to `policy`,
+    /// in their original order (i.e. not reversed).
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_rev_with(&mut self, policy: UnorderedPolicy)
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Sorts the slice, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
+    /// the total order will be put at the front or the back according to `policy`, in their original order.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_by_key_with<B, F>(&mut self, f: F, policy: UnorderedPolicy)
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
     /// Sort the slice. Values outside the ordered subset are put at the end.
     ///
     /// This is equivalent to `self.ord_subset_sort_by(|a,b| a.partial_cmp(b).unwrap())`
@@ -126,6 +716,18 @@ pub trait OrdSubsetSliceExt<T> {
         T: OrdSubset,
         F: FnMut(&T, &T) -> Ordering;
 
+    /// Sorts the slice in reverse order, using `compare` to order elements. `compare` describes the ascending order,
+    /// its arguments are swapped internally. Values outside the total order are put at the end.
+    /// `compare` will not be called on them.
+    ///
+    /// This delegates to `.sort_by_unstable()` in the std library. See [official docs](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by_unstable) for
+    /// time and space complexity of the current implementation.
+    fn ord_subset_sort_unstable_rev_by<F>(&mut self, compare: F)
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering;
+
     /// Sorts the slice, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
     /// the total order will be put at the end.
     ///
@@ -137,213 +739,2410 @@ pub trait OrdSubsetSliceExt<T> {
         B: OrdSubset,
         F: FnMut(&T) -> B;
 
-    /// Binary search a sorted slice for a given element. Values outside the ordered subset need to be at the end of the slice.
+    /// Like `ord_subset_sort_unstable_by_key`, but the key function returns a reference into the element.
+    /// This allows sorting by keys like a `String` field without cloning them.
+    fn ord_subset_sort_unstable_by_key_ref<B, F>(&mut self, f: F)
+    where
+        Self: AsMut<[T]>,
+        B: ?Sized + OrdSubset,
+        F: for<'a> FnMut(&'a T) -> &'a B;
+
+    /// Sorts the slice by a key that is computed exactly once per element and stored in `keys`.
+    /// Entries mapping to values outside the total order will be put at the end.
     ///
-    /// If the value is found then Ok is returned, containing the index of the matching element; if the value is not found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    /// This is an alternative to `.sort_by_cached_key()` that doesn't allocate and is available without `std`.
+    /// The caller provides the scratch space for the keys. Afterwards, it holds the keys in the new order of the slice.
+    /// The sort is an in-place heapsort, it takes O(n log n) time.
     ///
     /// # Example
     ///
-    /// Looks up a series of five elements. The first is found, with a uniquely determined position; the second and third are not found; the fourth could match any position in `[1,4]`.
-    ///
     /// ```
     /// use ord_subset::OrdSubsetSliceExt;
-    /// use std::f64;
-    ///
-    /// let s = [0., 1., 1., 1., 1., 2., 3., 5., 8., 13., 21., 34., 55., f64::NAN, f64::NAN];
     ///
-    /// assert_eq!(s.ord_subset_binary_search(&13.),  Ok(9));
-    /// assert_eq!(s.ord_subset_binary_search(&4.),   Err(7));
-    /// assert_eq!(s.ord_subset_binary_search(&100.), Err(13));
-    /// let r = s.ord_subset_binary_search(&1.);
-    /// assert!(match r { Ok(1...4) => true, _ => false, });
-    ///	assert_eq!(s.ord_subset_binary_search(&f64::INFINITY), Err(13));
+    /// let mut s = [3u32, 1, 2];
+    /// let mut keys = [0.0; 3];
+    /// s.ord_subset_sort_unstable_by_key_into(&mut keys, |&x| 1.0 / x as f64);
+    /// assert_eq!(s, [3, 2, 1]);
     /// ```
     ///
     /// # Panics
     ///
-    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
-    fn ord_subset_binary_search(&self, x: &T) -> Result<usize, usize>
+    /// Panics if `keys` and the slice don't have the same length. Also panics when `a.partial_cmp(b)` returns `None` for two keys `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_sort_unstable_by_key_into<B, F>(&mut self, keys: &mut [B], f: F)
     where
-        T: OrdSubset;
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
 
-    /// Binary search a sorted slice with a comparator function.
+    /// Sorts a slice of `f32` or `f64` like `ord_subset_sort_unstable`, but faster. NaNs are put at the end.
     ///
-    /// The comparator function should implement an order consistent with the sort order of the underlying slice, returning an order code that indicates whether its argument is Less, Equal or Greater the desired target. The comparator will only be called for values inside the total order.
+    /// This sorts by the IEEE 754 total order, which only needs integer comparisons, and moves the NaNs
+    /// with the sign bit set from the front to the end afterwards. As a consequence, `-0.0` is sorted before `0.0`.
     ///
-    /// It's imperative, that the comparator function doesn't compare its arguments with values outside the total order. This will result in bogus output which cannot be caught by this function.
+    /// # Example
     ///
-    /// If a matching value is found then returns Ok, containing the index for the matched element; if no match is found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
-    fn ord_subset_binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut s = [5.0, std::f64::NAN, 3.0, -std::f64::NAN, 2.0];
+    /// s.ord_subset_sort_floats();
+    /// assert_eq!(&s[..3], &[2.0, 3.0, 5.0]);
+    /// assert!(s[3].is_nan() && s[4].is_nan());
+    /// ```
+    fn ord_subset_sort_floats(&mut self)
     where
-        T: OrdSubset,
-        F: FnMut(&T) -> Ordering;
+        Self: AsMut<[T]>,
+        T: OrdSubsetFloat;
 
-    /// Binary search a sorted slice with a key extraction function.
+    /// Sorts the slice in reverse order, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
+    /// the total order will be put at the end.
     ///
-    /// Assumes that the slice is sorted by the key, for instance with `ord_subset_sort_by_key` using the same key extraction function.
+    /// This delegates to `.sort_by_unstable()` in the std library. See [official docs](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by_unstable) for
+    /// time and space complexity of the current implementation.
+    fn ord_subset_sort_unstable_rev_by_key<B, F>(&mut self, f: F)
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Sort the slice. Values outside the ordered subset are put at the front or the back according to `policy`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_sort_unstable_with(&mut self, policy: UnorderedPolicy)
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Sort the slice in reverse order. Values outside the ordered subset are put at the front or the back according to `policy`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_sort_unstable_rev_with(&mut self, policy: UnorderedPolicy)
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Sorts the slice, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
+    /// the total order will be put at the front or the back according to `policy`.
+    fn ord_subset_sort_unstable_by_key_with<B, F>(&mut self, f: F, policy: UnorderedPolicy)
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Like `.ord_subset_sort()`, but returns the number of values inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_counted(&mut self) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Like `.ord_subset_sort_rev()`, but returns the number of values inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_rev_counted(&mut self) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Like `.ord_subset_sort_by()`, but returns the number of values inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_by_counted<F>(&mut self, compare: F) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Like `.ord_subset_sort_by_key()`, but returns the number of entries whose key is inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_by_key_counted<B, F>(&mut self, f: F) -> usize
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Like `.ord_subset_sort_unstable()`, but returns the number of values inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    fn ord_subset_sort_unstable_counted(&mut self) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Like `.ord_subset_sort_unstable_rev()`, but returns the number of values inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    fn ord_subset_sort_unstable_rev_counted(&mut self) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Like `.ord_subset_sort_unstable_by()`, but returns the number of values inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    fn ord_subset_sort_unstable_by_counted<F>(&mut self, compare: F) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Like `.ord_subset_sort_unstable_by_key()`, but returns the number of entries whose key is inside the total order.
+    /// The sorted part of the slice is `&self[..n]`.
+    fn ord_subset_sort_unstable_by_key_counted<B, F>(&mut self, f: F) -> usize
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Binary search a sorted slice for a given element. Values outside the ordered subset need to be at the end of the slice.
+    ///
+    /// If the value is found then Ok is returned, containing the index of the matching element; if the value is not found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Example
+    ///
+    /// Looks up a series of five elements. The first is found, with a uniquely determined position; the second and third are not found; the fourth could match any position in `[1,4]`.
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    /// use std::f64;
+    ///
+    /// let s = [0., 1., 1., 1., 1., 2., 3., 5., 8., 13., 21., 34., 55., f64::NAN, f64::NAN];
+    ///
+    /// assert_eq!(s.ord_subset_binary_search(&13.),  Ok(9));
+    /// assert_eq!(s.ord_subset_binary_search(&4.),   Err(7));
+    /// assert_eq!(s.ord_subset_binary_search(&100.), Err(13));
+    /// let r = s.ord_subset_binary_search(&1.);
+    /// assert!(match r { Ok(1...4) => true, _ => false, });
+    ///	assert_eq!(s.ord_subset_binary_search(&f64::INFINITY), Err(13));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset;
+
+    /// Binary search a sorted slice with a comparator function.
+    ///
+    /// The comparator function should implement an order consistent with the sort order of the underlying slice, returning an order code that indicates whether its argument is Less, Equal or Greater the desired target. The comparator will only be called for values inside the total order.
+    ///
+    /// It's imperative, that the comparator function doesn't compare its arguments with values outside the total order. This will result in bogus output which cannot be caught by this function.
+    ///
+    /// If a matching value is found then returns Ok, containing the index for the matched element; if no match is found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    fn ord_subset_binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+        F: FnMut(&T) -> Ordering;
+
+    /// Binary search the first `n_ordered` elements of a sorted slice with a comparator function.
+    /// `n_ordered` is the number of values inside the total order, as returned by `ord_subset_ordered_prefix_len`.
+    ///
+    /// `ord_subset_binary_search_by` computes that length on every call. Passing it in allows repeated searches on the same slice to skip that step.
+    /// The comparator is only called for the first `n_ordered` elements and indices refer to the whole slice.
+    /// For a slice sorted by key, pass the length from `ord_subset_ordered_prefix_len_by_key`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [1.0, 2.0, 4.0, std::f64::NAN];
+    /// let n_ordered = s.ord_subset_ordered_prefix_len();
+    /// assert_eq!(s.ord_subset_binary_search_in_prefix(n_ordered, |x| x.partial_cmp(&2.0).unwrap()), Ok(1));
+    /// assert_eq!(s.ord_subset_binary_search_in_prefix(n_ordered, |x| x.partial_cmp(&5.0).unwrap()), Err(3));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_ordered` is greater than the length of the slice.
+    fn ord_subset_binary_search_in_prefix<F>(&self, n_ordered: usize, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering;
+
+    /// Binary search a sorted slice with a key extraction function.
+    ///
+    /// Assumes that the slice is sorted by the key, for instance with `ord_subset_sort_by_key` using the same key extraction function.
     ///
     /// If a matching value is found then returns `Ok`, containing the index for the matched element; if no match is found then `Err` is returned, containing the index where a matching element could be inserted while maintaining sorted order.
     fn ord_subset_binary_search_by_key<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
     where
         B: OrdSubset,
-        F: FnMut(&T) -> B;
+        F: FnMut(&T) -> B;
+
+    /// Like `ord_subset_binary_search_by_key`, but the key function returns a reference into the element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two keys `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search_by_key_ref<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
+    where
+        B: ?Sized + OrdSubset,
+        F: for<'a> FnMut(&'a T) -> &'a B;
+
+    /// Like `ord_subset_binary_search_by_key_ref`, but the needle may be a borrowed form of the key, like `HashMap::get`.
+    /// This allows e.g. searching by a `&str` when the key function returns a reference to a `String` field, without allocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// struct Entry { name: String, score: f64 }
+    /// let v = vec![Entry { name: "a".into(), score: 1.0 }, Entry { name: "b".into(), score: 0.5 }];
+    /// assert_eq!(v.ord_subset_binary_search_by_key_borrowed("b", |e| &e.name), Ok(1));
+    /// assert_eq!(v.ord_subset_binary_search_by_key_borrowed("ab", |e| e.name.as_str()), Err(1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two keys `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search_by_key_borrowed<Q, B, F>(&self, q: &Q, f: F) -> Result<usize, usize>
+    where
+        Q: ?Sized + OrdSubset,
+        B: ?Sized + Borrow<Q>,
+        F: for<'a> FnMut(&'a T) -> &'a B;
+
+    /// Binary search a slice sorted in reverse order for a given element. Values outside the ordered subset need to be at the end of the slice.
+    ///
+    /// If a matching value is found then returns Ok, containing the index for the matched element; if no match is found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search_rev(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset;
+
+    /// Like `ord_subset_binary_search`, but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SearchError::NeedleUnordered` if `x` is outside the total order and `SearchError::ContractViolation`
+    /// if `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::{OrdSubsetSliceExt, SearchError};
+    /// use std::f64;
+    ///
+    /// let s = [1.0, 2.0, 3.0, f64::NAN];
+    /// assert_eq!(s.try_ord_subset_binary_search(&2.0), Ok(Ok(1)));
+    /// assert_eq!(s.try_ord_subset_binary_search(&f64::NAN), Err(SearchError::NeedleUnordered));
+    /// ```
+    fn try_ord_subset_binary_search(&self, x: &T) -> Result<Result<usize, usize>, SearchError>
+    where
+        T: OrdSubset;
+
+    /// Like `ord_subset_binary_search_by_key`, but returns an error instead of panicking.
+    /// See `try_ord_subset_binary_search` for the errors.
+    fn try_ord_subset_binary_search_by_key<B, F>(&self, b: &B, f: F) -> Result<Result<usize, usize>, SearchError>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Like `ord_subset_binary_search_rev`, but returns an error instead of panicking.
+    /// See `try_ord_subset_binary_search` for the errors.
+    fn try_ord_subset_binary_search_rev(&self, x: &T) -> Result<Result<usize, usize>, SearchError>
+    where
+        T: OrdSubset;
+
+    /// Binary search a sorted slice for the range of elements equal to `x`. Values outside the ordered subset need to be at the end of the slice
+    /// and are never part of the range.
+    ///
+    /// If no element is equal to `x`, the returned range is empty and starts at the index where `x` could be inserted while maintaining sorted order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    /// use std::f64;
+    ///
+    /// let s = [0., 1., 1., 1., 1., 2., 3., 5., 8., 13., 21., 34., 55., f64::NAN, f64::NAN];
+    ///
+    /// assert_eq!(s.ord_subset_binary_search_range(&1.),   1..5);
+    /// assert_eq!(s.ord_subset_binary_search_range(&13.),  9..10);
+    /// assert_eq!(s.ord_subset_binary_search_range(&4.),   7..7);
+    /// assert_eq!(s.ord_subset_binary_search_range(&100.), 13..13);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search_range(&self, x: &T) -> Range<usize>
+    where
+        T: OrdSubset;
+
+    /// Binary search a sorted slice for the first element equal to `x`. Values outside the ordered subset need to be at the end of the slice.
+    ///
+    /// Unlike `ord_subset_binary_search`, the result is deterministic when there are multiple matches.
+    /// If no match is found then `Err` is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    /// use std::f64;
+    ///
+    /// let s = [0., 1., 1., 1., 2., f64::NAN];
+    ///
+    /// assert_eq!(s.ord_subset_binary_search_first(&1.), Ok(1));
+    /// assert_eq!(s.ord_subset_binary_search_last(&1.), Ok(3));
+    /// assert_eq!(s.ord_subset_binary_search_first(&1.5), Err(4));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search_first(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset;
+
+    /// Binary search a sorted slice for the last element equal to `x`. Values outside the ordered subset need to be at the end of the slice.
+    ///
+    /// Unlike `ord_subset_binary_search`, the result is deterministic when there are multiple matches.
+    /// If no match is found then `Err` is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search_last(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset;
+
+    /// Binary search a slice sorted by the key extraction function for the first element whose key is equal to `b`.
+    ///
+    /// If no match is found then `Err` is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is outside of the total order.
+    fn ord_subset_binary_search_first_by_key<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Binary search a slice sorted by the key extraction function for the last element whose key is equal to `b`.
+    ///
+    /// If no match is found then `Err` is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is outside of the total order.
+    fn ord_subset_binary_search_last_by_key<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the index of the element closest to `x` in a sorted float slice together with the element itself.
+    /// Values outside the ordered subset need to be at the end of the slice and are never returned.
+    ///
+    /// If `x` lies exactly in the middle between two neighbours, the one with the lower index is returned.
+    /// Returns `None` if `x` is outside the total order or if the slice contains no values inside of it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let samples = [0.0, 1.0, 2.5, 4.0, std::f64::NAN];
+    /// assert_eq!(samples.ord_subset_closest(&1.6), Some((1, &1.0)));
+    /// assert_eq!(samples.ord_subset_closest(&1.75), Some((1, &1.0)));
+    /// assert_eq!(samples.ord_subset_closest(&100.0), Some((3, &4.0)));
+    /// assert_eq!(samples.ord_subset_closest(&std::f64::NAN), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_closest(&self, x: &T) -> Option<(usize, &T)>
+    where
+        T: OrdSubsetFloat + Into<f64>;
+
+    /// Like `ord_subset_closest`, but for any sorted `T` with a distance function.
+    /// `dist(x, candidate)` is only called for the two neighbours of `x` and should grow with the distance from `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_closest_by<D, F>(&self, x: &T, dist: F) -> Option<(usize, &T)>
+    where
+        T: OrdSubset,
+        D: PartialOrd,
+        F: FnMut(&T, &T) -> D;
+
+    /// Returns `true` if a sorted slice contains an element equal to `x`, using binary search. Values outside the ordered subset need to be at the end of the slice.
+    ///
+    /// Unlike `ord_subset_binary_search`, this doesn't panic if `x` is outside the total order, it returns `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    /// use std::f64;
+    ///
+    /// let s = [0., 1., 2., 3., 5., f64::NAN];
+    ///
+    /// assert!(s.ord_subset_contains(&3.));
+    /// assert!(!s.ord_subset_contains(&4.));
+    /// assert!(!s.ord_subset_contains(&f64::NAN));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_contains(&self, x: &T) -> bool
+    where
+        T: OrdSubset;
+
+    /// Returns `true` if a slice sorted by the key extraction function contains an element whose key is equal to `b`, using binary search.
+    ///
+    /// Returns `false` if `b` is outside the total order.
+    fn ord_subset_contains_by_key<B, F>(&self, b: &B, f: F) -> bool
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the number of values inside the total order of a slice in which all values outside the ordered subset are at the end,
+    /// such as a sorted slice. The ordered part of the slice is `&self[..n]`.
+    ///
+    /// This is a binary search and takes O(log n) time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut s = [5.0, std::f64::NAN, 3.0, 2.0];
+    /// s.ord_subset_sort_unstable();
+    /// let ordered = &s[..s.ord_subset_ordered_prefix_len()];
+    /// assert_eq!(ordered, &[2.0, 3.0, 5.0]);
+    /// ```
+    fn ord_subset_ordered_prefix_len(&self) -> usize
+    where
+        T: OrdSubset;
+
+    /// Returns the number of entries whose key is inside the total order in a slice in which all entries with keys outside the ordered subset are at the end,
+    /// such as a slice sorted by the same key.
+    ///
+    /// This is a binary search and takes O(log n) time.
+    fn ord_subset_ordered_prefix_len_by_key<B, F>(&self, f: F) -> usize
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns `true` if no value inside the total order follows a value outside of it,
+    /// i.e. if all values outside the ordered subset are at the end of the slice.
+    ///
+    /// This is the layout the binary searches and `ord_subset_ordered_prefix_len` require. The ordered values don't need to be sorted.
+    /// It takes O(n) time, but is cheaper than checking for sortedness.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let nan = std::f64::NAN;
+    /// assert!([5.0, 3.0, nan, nan].ord_subset_is_partitioned());
+    /// assert!(![5.0, nan, 3.0].ord_subset_is_partitioned());
+    /// ```
+    fn ord_subset_is_partitioned(&self) -> bool
+    where
+        T: OrdSubset;
+
+    /// Returns `true` if no entry with a key inside the total order follows an entry with a key outside of it.
+    /// This is the layout `ord_subset_binary_search_by_key` and `ord_subset_ordered_prefix_len_by_key` require.
+    ///
+    /// The key function is called at most once per element.
+    fn ord_subset_is_partitioned_by_key<B, F>(&self, f: F) -> bool
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Moves all values outside the ordered subset to the end of the slice without sorting.
+    /// Returns the number of values inside the total order, i.e. the index of the first unordered value.
+    ///
+    /// The partition is stable, both ordered and unordered values keep their relative order.
+    /// With the `alloc` feature, which `std` implies, it takes O(n) time and O(n) extra space for the new order of the indices.
+    /// Without it, the partition is done in place and takes O(n log n) time in the worst case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut s = [5.0, std::f64::NAN, 3.0, 2.0];
+    /// let n = s.ord_subset_partition_unordered();
+    /// assert_eq!(&s[..n], &[5.0, 3.0, 2.0]);
+    /// assert!(s[n].is_nan());
+    /// ```
+    fn ord_subset_partition_unordered(&mut self) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Moves all entries whose key is outside the ordered subset to the end of the slice without sorting.
+    /// Returns the number of entries with keys inside the total order. The key function is called exactly once per element.
+    ///
+    /// The partition is stable, both sets of entries keep their relative order.
+    /// With the `alloc` feature, which `std` implies, it takes O(n) time and O(n) extra space for the new order of the indices.
+    /// Without it, the partition is done in place and takes O(n log n) time in the worst case.
+    fn ord_subset_partition_unordered_by_key<B, F>(&mut self, f: F) -> usize
+    where
+        Self: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Moves all values outside the ordered subset to the end of the slice by swapping.
+    /// Returns the number of values inside the total order, i.e. the index of the first unordered value.
+    ///
+    /// Values inside the total order keep their relative order, values outside of it may be reordered.
+    /// It takes O(n) time in place and never allocates. Unlike `ord_subset_partition_unordered`,
+    /// it doesn't keep the order of the unordered tail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut s = [5.0, std::f64::NAN, 3.0, 2.0];
+    /// let n = s.ord_subset_swap_unordered_to_end();
+    /// assert_eq!(&s[..n], &[5.0, 3.0, 2.0]);
+    /// assert!(s[n].is_nan());
+    /// ```
+    fn ord_subset_swap_unordered_to_end(&mut self) -> usize
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Returns the maximum element of the slice. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [2.0, std::f64::NAN, 5.0, 3.0];
+    /// assert_eq!(s.ord_subset_max(), Some(&5.0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_max(&self) -> Option<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the minimum element of the slice. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_min(&self) -> Option<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the maximum element of the slice with respect to `compare`. Values outside the ordered subset are ignored,
+    /// `compare` will not be called on them.
+    ///
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    fn ord_subset_max_by<F>(&self, compare: F) -> Option<&T>
+    where
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Returns the minimum element of the slice with respect to `compare`. Values outside the ordered subset are ignored,
+    /// `compare` will not be called on them.
+    ///
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    fn ord_subset_min_by<F>(&self, compare: F) -> Option<&T>
+    where
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Returns the element that gives the maximum value from the specified function.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    fn ord_subset_max_by_key<B, F>(&self, f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the element that gives the minimum value from the specified function.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    fn ord_subset_min_by_key<B, F>(&self, f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the index of the maximum element of the slice. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the index of the last element if the comparison determines multiple elements to be equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [2.0, std::f64::NAN, 5.0, 3.0, 5.0];
+    /// assert_eq!(s.ord_subset_max_index(), Some(4));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_max_index(&self) -> Option<usize>
+    where
+        T: OrdSubset;
+
+    /// Returns the index of the minimum element of the slice. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the index of the first element if the comparison determines multiple elements to be equally minimum.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_min_index(&self) -> Option<usize>
+    where
+        T: OrdSubset;
+
+    /// Returns the index of the maximum element of the slice together with the element. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [2.0, std::f64::NAN, 5.0, 3.0];
+    /// assert_eq!(s.ord_subset_max_with_index(), Some((2, &5.0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_max_with_index(&self) -> Option<(usize, &T)>
+    where
+        T: OrdSubset;
+
+    /// Returns the index of the minimum element of the slice together with the element. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_min_with_index(&self) -> Option<(usize, &T)>
+    where
+        T: OrdSubset;
+
+    /// Returns all elements comparing equal to the maximum, in the order of the slice. Values outside the ordered subset are ignored.
+    /// Returns an empty `Vec` if the slice contains no values inside the total order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [2.0, 5.0, std::f64::NAN, 5.0, 3.0];
+    /// assert_eq!(s.ord_subset_max_set(), [&5.0, &5.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_max_set(&self) -> Vec<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns all elements comparing equal to the minimum, in the order of the slice. Values outside the ordered subset are ignored.
+    /// Returns an empty `Vec` if the slice contains no values inside the total order.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_min_set(&self) -> Vec<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the index of the element that gives the maximum value from the specified function.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// Returns the index of the last element if the comparison determines multiple elements to be equally maximum.
+    fn ord_subset_max_index_by_key<B, F>(&self, f: F) -> Option<usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the index of the element that gives the minimum value from the specified function.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// Returns the index of the first element if the comparison determines multiple elements to be equally minimum.
+    fn ord_subset_min_index_by_key<B, F>(&self, f: F) -> Option<usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the indices of the elements that give the minimum and the maximum value from the specified function as `(argmin, argmax)`.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    /// This takes a single pass over the slice and calls the key function once per element.
+    ///
+    /// Returns the index of the first element if the comparison determines multiple elements to be equally minimum
+    /// and the index of the last element if multiple elements are equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [(1, 2.0), (2, std::f64::NAN), (3, -1.0), (4, 7.0)];
+    /// assert_eq!(s.ord_subset_extrema_by_key(|&(_, k)| k), Some((2, 3)));
+    /// ```
+    fn ord_subset_extrema_by_key<B, F>(&self, f: F) -> Option<(usize, usize)>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the minimum and the maximum element as `(min, max)` in a single pass. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the first element if multiple elements are equally minimum and the last element if multiple elements are equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [2.0, std::f64::NAN, -1.0, 7.0];
+    /// assert_eq!(s.ord_subset_minmax(), Some((&-1.0, &7.0)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_minmax(&self) -> Option<(&T, &T)>
+    where
+        T: OrdSubset;
+
+    /// Returns the indices of the minimum and the maximum element as `(argmin, argmax)` in a single pass. Values outside the ordered subset are ignored.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// Returns the index of the first element if multiple elements are equally minimum
+    /// and the index of the last element if multiple elements are equally maximum.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_minmax_indices(&self) -> Option<(usize, usize)>
+    where
+        T: OrdSubset;
+
+    /// Returns the elements that give the minimum and the maximum value from the specified function as `(min, max)`.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    /// This takes a single pass over the slice and calls the key function once per element.
+    /// For the indices, use `ord_subset_extrema_by_key`.
+    ///
+    /// Returns the first element if multiple elements are equally minimum and the last element if multiple elements are equally maximum.
+    fn ord_subset_minmax_by_key<B, F>(&self, f: F) -> Option<(&T, &T)>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Computes the rank of each element among the values inside the total order, i.e. the number of ordered
+    /// elements that are strictly smaller. Equal elements share a rank (competition ranking, "0113").
+    /// Values outside the ordered subset are not ranked and don't count towards the rank of others.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [5.0, std::f64::NAN, 3.0, 5.0, 9.0];
+    /// assert_eq!(s.ord_subset_rank(), vec![Some(1), None, Some(0), Some(1), Some(3)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_rank(&self) -> Vec<Option<usize>>
+    where
+        T: OrdSubset;
+
+    /// Returns the `q`-quantile of a sorted slice by the nearest-rank method. Values outside the ordered subset need to be at the end of the slice
+    /// and don't count towards the rank.
+    ///
+    /// Returns `None` if `q` is not within `[0, 1]` or if the slice contains no values inside the total order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    /// use std::f64;
+    ///
+    /// let s = [1., 2., 3., 4., 5., f64::NAN, f64::NAN];
+    ///
+    /// assert_eq!(s.ord_subset_quantile(0.0), Some(&1.));
+    /// assert_eq!(s.ord_subset_quantile(0.5), Some(&3.));
+    /// assert_eq!(s.ord_subset_quantile(0.9), Some(&5.));
+    /// assert_eq!(s.ord_subset_quantile(1.5), None);
+    /// ```
+    fn ord_subset_quantile(&self, q: f64) -> Option<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the `p`-th percentile of a sorted slice by the nearest-rank method.
+    /// This is like `self.ord_subset_quantile(p / 100.0)`, but computes the rank as `p * n / 100` to avoid the rounding error of the division.
+    ///
+    /// Returns `None` if `p` is not within `[0, 100]` or if the slice contains no values inside the total order.
+    fn ord_subset_percentile(&self, p: f64) -> Option<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the `p`-th percentile of a sorted float slice, interpolating linearly between the two closest ranks
+    /// (type 7 in Hyndman & Fan, the default of R and NumPy). NaNs need to be at the end of the slice and are ignored.
+    ///
+    /// Returns `None` if `p` is not within `[0, 100]` or if the slice contains only NaNs.
+    /// This doesn't modify the slice or allocate. In debug builds, it asserts that the slice is sorted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [1.0, 2.0, 4.0, 8.0, std::f64::NAN];
+    /// assert_eq!(s.ord_subset_percentile_sorted(50.0), Some(3.0));
+    /// assert_eq!(s.ord_subset_percentile_sorted(100.0), Some(8.0));
+    /// ```
+    fn ord_subset_percentile_sorted(&self, p: f64) -> Option<f64>
+    where
+        T: OrdSubsetFloat + Into<f64>;
+
+    /// Returns the `n`-th smallest value inside the total order, counting from zero. Values outside the ordered subset
+    /// are ignored and don't count towards `n`. Returns `None` if there are no more than `n` values inside the total order.
+    ///
+    /// This uses selection (`select_nth_unstable_by`) in O(n) time and rearranges the slice like `ord_subset_median`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let nan = std::f64::NAN;
+    /// let mut s = [5.0, nan, 1.0, 4.0, nan, 2.0, 3.0];
+    /// assert_eq!(s.ord_subset_nth_smallest(0), Some(&1.0));
+    /// assert_eq!(s.ord_subset_nth_smallest(2), Some(&3.0));
+    /// assert_eq!(s.ord_subset_nth_smallest(5), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_nth_smallest(&mut self, n: usize) -> Option<&T>
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Returns the `n`-th largest value inside the total order, counting from zero.
+    /// Like `ord_subset_nth_smallest`, but counting from the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut s = [5.0, std::f64::NAN, 1.0, 4.0, 2.0, 3.0];
+    /// assert_eq!(s.ord_subset_nth_largest(0), Some(&5.0));
+    /// assert_eq!(s.ord_subset_nth_largest(1), Some(&4.0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_nth_largest(&mut self, n: usize) -> Option<&T>
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Returns the median of the values inside the total order. Values outside the ordered subset are ignored
+    /// and don't count towards the number of elements. For an even number of ordered values, the lower of the two middle values is returned.
+    /// Returns `None` if the slice contains no values inside the total order.
+    ///
+    /// The median is found by selection in O(n) time, the slice doesn't need to be sorted. This rearranges the slice:
+    /// all values outside the total order are moved to the end and the ordered values are partitioned around the median.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let nan = std::f64::NAN;
+    /// let mut s = [5.0, nan, 1.0, 4.0, nan, 2.0, 3.0, nan];
+    /// assert_eq!(s.ord_subset_median(), Some(&3.0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_median(&mut self) -> Option<&T>
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset;
+
+    /// Returns the median of the non-NaN floats. For an even number of them, the mean of the two middle values is returned.
+    /// Returns `None` if all values are NaN or if the two middle values are `-∞` and `∞`, whose mean is undefined.
+    ///
+    /// Like `ord_subset_median`, this takes O(n) time and rearranges the slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut s = [4.0f32, std::f32::NAN, 1.0, 2.0, 3.0];
+    /// assert_eq!(s.ord_subset_median_interpolated(), Some(2.5));
+    /// ```
+    fn ord_subset_median_interpolated(&mut self) -> Option<f64>
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubsetFloat + Into<f64>;
+
+    /// Returns the `q`-quantiles for all `qs` by the nearest-rank method. Values outside the ordered subset are ignored
+    /// and don't count towards the rank.
+    ///
+    /// The slice is sorted with `ord_subset_sort_unstable` once, then each quantile is looked up like with `ord_subset_quantile`.
+    /// All entries are `None` if the slice contains no values inside the total order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let mut latencies = [12.0, std::f64::NAN, 30.0, 15.0, 11.0, 14.0, 13.0, 90.0, 16.0, 17.0, 18.0];
+    /// let q = latencies.ord_subset_quantiles(&[0.5, 0.9, 0.99]);
+    /// assert_eq!(q, [Some(15.0), Some(30.0), Some(90.0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any `q` is not within `[0, 1]`, including NaN. This is checked before the slice is sorted.
+    /// Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_quantiles(&mut self, qs: &[f64]) -> Vec<Option<T>>
+    where
+        Self: AsMut<[T]>,
+        T: OrdSubset + Clone;
+
+    /// Returns an iterator over runs of consecutive equal elements, intended for sorted slices.
+    /// Consecutive values outside the ordered subset are yielded together as a single run,
+    /// so the unordered tail of a sorted slice forms the last chunk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let s = [1., 1., 2., 3., 3., 3., std::f64::NAN, std::f64::NAN];
+    /// let lengths: Vec<_> = s.ord_subset_chunk_by().map(|chunk| chunk.len()).collect();
+    /// assert_eq!(lengths, [2, 1, 3, 2]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_chunk_by<'a>(&'a self) -> ChunkBy<'a, T>
+    where
+        T: OrdSubset;
+
+    /// Returns the maximum of every window of `w` consecutive elements. Values outside the ordered subset are ignored,
+    /// the entry of a window without values inside the total order is `None`.
+    ///
+    /// There are as many windows as for `.windows(w)`. This takes O(n) time, independent of `w`.
+    /// Returns the last element if the comparison determines multiple elements in a window to be equally maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let nan = std::f64::NAN;
+    /// let s = [1.0, 3.0, nan, nan, 2.0];
+    /// assert_eq!(s.ord_subset_sliding_max(2), [Some(&3.0), Some(&3.0), None, Some(&2.0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `w` is 0. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_sliding_max(&self, w: usize) -> Vec<Option<&T>>
+    where
+        T: OrdSubset;
+
+    /// Returns the minimum of every window of `w` consecutive elements. Values outside the ordered subset are ignored,
+    /// the entry of a window without values inside the total order is `None`.
+    ///
+    /// There are as many windows as for `.windows(w)`. This takes O(n) time, independent of `w`.
+    /// Returns the first element if the comparison determines multiple elements in a window to be equally minimum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `w` is 0. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_sliding_min(&self, w: usize) -> Vec<Option<&T>>
+    where
+        T: OrdSubset;
+
+    /// Merges two slices sorted with `ord_subset_sort` into a new sorted `Vec`.
+    /// Values outside the ordered subset are put at the end, those of `self` first. Both keep their original order.
+    ///
+    /// The merge is stable, elements of `self` come before equal elements of `other`. It takes O(n + m) time.
+    /// If either slice isn't sorted, the order of the result is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetSliceExt;
+    ///
+    /// let nan = std::f64::NAN;
+    /// let merged = [1.0, 4.0, nan].ord_subset_merge(&[2.0, 3.0, 5.0]);
+    /// assert_eq!(&merged[..5], &[1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// assert!(merged[5].is_nan());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    #[cfg(feature = "std")]
+    fn ord_subset_merge(&self, other: &[T]) -> Vec<T>
+    where
+        T: OrdSubset + Clone;
+
+    /// Merges two slices sorted with `ord_subset_sort_by_key` using the same key function into a new sorted `Vec`.
+    /// Elements with keys outside the ordered subset are put at the end, those of `self` first.
+    ///
+    /// The merge is stable, elements of `self` come before elements of `other` with equal keys.
+    /// The key function may be called multiple times per element.
+    #[cfg(feature = "std")]
+    fn ord_subset_merge_by_key<B, F>(&self, other: &[T], f: F) -> Vec<T>
+    where
+        T: Clone,
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Like `ord_subset_merge`, but appends the result to `out`. This allows reusing the allocation when merging repeatedly.
+    #[cfg(feature = "std")]
+    fn ord_subset_merge_into(&self, other: &[T], out: &mut Vec<T>)
+    where
+        T: OrdSubset + Clone;
+}
+
+impl<T, U> OrdSubsetSliceExt<T> for U
+where
+    U: AsRef<[T]>,
+{
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort(&mut self)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        self.as_mut().ord_subset_sort_by(|a, b| a.cmp_unwrap(b))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_by<F>(&mut self, compare: F)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !x.is_outside_order());
+        slice[ordered].sort_by(compare)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_rev_by<F>(&mut self, mut compare: F)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_mut().ord_subset_sort_by(|a, b| compare(b, a))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_rev(&mut self)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        self.as_mut().ord_subset_sort_by(|a, b| b.cmp_unwrap(a))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_by_key<B, F>(&mut self, mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !f(x).is_outside_order());
+        slice[ordered].sort_by(|a, b| f(a).cmp_unwrap(&f(b)))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_by_key_ref<B, F>(&mut self, mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: ?Sized + OrdSubset,
+        F: for<'a> FnMut(&'a T) -> &'a B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !f(x).is_outside_order());
+        slice[ordered].sort_by(|a, b| f(a).cmp_unwrap(f(b)))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_rev_by_key<B, F>(&mut self, mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !f(x).is_outside_order());
+        slice[ordered].sort_by(|a, b| f(b).cmp_unwrap(&f(a)))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn to_ord_subset_sorted_vec(&self) -> Vec<T>
+    where
+        T: OrdSubset + Clone,
+    {
+        let mut v = self.as_ref().to_vec();
+        v.ord_subset_sort();
+        v
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn to_ord_subset_sorted_vec_rev(&self) -> Vec<T>
+    where
+        T: OrdSubset + Clone,
+    {
+        let mut v = self.as_ref().to_vec();
+        v.ord_subset_sort_rev();
+        v
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn to_ord_subset_sorted_vec_by_key<B, F>(&self, f: F) -> Vec<T>
+    where
+        T: Clone,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let mut v = self.as_ref().to_vec();
+        v.ord_subset_sort_by_key(f);
+        v
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_argsort(&self) -> Vec<usize>
+    where
+        T: OrdSubset,
+    {
+        let slice = self.as_ref();
+        let mut perm: Vec<usize> = (0..slice.len()).collect();
+        let ordered = partition_ordered(&mut perm, UnorderedPolicy::Back, true, |&i| !slice[i].is_outside_order());
+        perm[ordered].sort_by(|&a, &b| slice[a].cmp_unwrap(&slice[b]));
+        perm
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_argsort_by_key<B, F>(&self, mut f: F) -> Vec<usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_ref();
+        let mut perm: Vec<usize> = (0..slice.len()).collect();
+        let ordered = partition_ordered(&mut perm, UnorderedPolicy::Back, true, |&i| !f(&slice[i]).is_outside_order());
+        perm[ordered].sort_by(|&a, &b| f(&slice[a]).cmp_unwrap(&f(&slice[b])));
+        perm
+    }
+
+    #[cfg(feature = "std")]
+    fn ord_subset_sort_together<V>(&mut self, values: &mut [V])
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        assert_eq!(slice.len(), values.len(), "values must have the same length as the slice");
+        let perm = slice.ord_subset_argsort();
+        apply_permutation(slice, &perm);
+        apply_permutation(values, &perm);
+    }
+
+    #[cfg(feature = "std")]
+    fn ord_subset_apply_permutation(&mut self, perm: &[usize])
+    where
+        U: AsMut<[T]>,
+    {
+        apply_permutation(self.as_mut(), perm)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_with(&mut self, policy: UnorderedPolicy)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, policy, true, |x| !x.is_outside_order());
+        slice[ordered].sort_by(|a, b| a.cmp_unwrap(b))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_rev_with(&mut self, policy: UnorderedPolicy)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, policy, true, |x| !x.is_outside_order());
+        slice[ordered].sort_by(|a, b| b.cmp_unwrap(a))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_by_key_with<B, F>(&mut self, mut f: F, policy: UnorderedPolicy)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, policy, true, |x| !f(x).is_outside_order());
+        slice[ordered].sort_by(|a, b| f(a).cmp_unwrap(&f(b)))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable(&mut self)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        self.as_mut()
+            .ord_subset_sort_unstable_by(|a, b| a.cmp_unwrap(b))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !x.is_outside_order());
+        slice[ordered].sort_unstable_by(compare)
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_rev_by<F>(&mut self, mut compare: F)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_mut()
+            .ord_subset_sort_unstable_by(|a, b| compare(b, a))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_rev(&mut self)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        self.as_mut()
+            .ord_subset_sort_unstable_by(|a, b| b.cmp_unwrap(a))
+    }
+
+    #[inline]
+    fn ord_subset_sort_floats(&mut self)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubsetFloat,
+    {
+        let slice = self.as_mut();
+        slice.sort_unstable_by(|a, b| a.total_order_cmp(b));
+        // negative NaNs are at the front
+        let n_front_nans = slice.iter().take_while(|x| x.is_outside_order()).count();
+        slice.rotate_left(n_front_nans);
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_key<B, F>(&mut self, mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !f(x).is_outside_order());
+        slice[ordered].sort_unstable_by(|a, b| f(a).cmp_unwrap(&f(b)))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_key_ref<B, F>(&mut self, mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: ?Sized + OrdSubset,
+        F: for<'a> FnMut(&'a T) -> &'a B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !f(x).is_outside_order());
+        slice[ordered].sort_unstable_by(|a, b| f(a).cmp_unwrap(f(b)))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_key_into<B, F>(&mut self, keys: &mut [B], mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        assert_eq!(
+            keys.len(),
+            slice.len(),
+            "key buffer must have the same length as the slice"
+        );
+        for (key, x) in keys.iter_mut().zip(slice.iter()) {
+            *key = f(x);
+        }
+        // keys outside the order go to the end
+        let mut n_ordered = 0;
+        for i in 0..keys.len() {
+            if !keys[i].is_outside_order() {
+                keys.swap(n_ordered, i);
+                slice.swap(n_ordered, i);
+                n_ordered += 1;
+            }
+        }
+        heapsort_by_keys(&mut keys[..n_ordered], &mut slice[..n_ordered]);
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_rev_by_key<B, F>(&mut self, mut f: F)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !f(x).is_outside_order());
+        slice[ordered].sort_unstable_by(|a, b| f(b).cmp_unwrap(&f(a)))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_with(&mut self, policy: UnorderedPolicy)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, policy, false, |x| !x.is_outside_order());
+        slice[ordered].sort_unstable_by(|a, b| a.cmp_unwrap(b))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_rev_with(&mut self, policy: UnorderedPolicy)
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, policy, false, |x| !x.is_outside_order());
+        slice[ordered].sort_unstable_by(|a, b| b.cmp_unwrap(a))
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_key_with<B, F>(&mut self, mut f: F, policy: UnorderedPolicy)
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, policy, false, |x| !f(x).is_outside_order());
+        slice[ordered].sort_unstable_by(|a, b| f(a).cmp_unwrap(&f(b)))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_counted(&mut self) -> usize
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !x.is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_by(|a, b| a.cmp_unwrap(b));
+        n_ordered
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_rev_counted(&mut self) -> usize
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !x.is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_by(|a, b| b.cmp_unwrap(a));
+        n_ordered
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_by_counted<F>(&mut self, compare: F) -> usize
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !x.is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_by(compare);
+        n_ordered
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sort_by_key_counted<B, F>(&mut self, mut f: F) -> usize
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, true, |x| !f(x).is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_by(|a, b| f(a).cmp_unwrap(&f(b)));
+        n_ordered
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_counted(&mut self) -> usize
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !x.is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_unstable_by(|a, b| a.cmp_unwrap(b));
+        n_ordered
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_rev_counted(&mut self) -> usize
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !x.is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_unstable_by(|a, b| b.cmp_unwrap(a));
+        n_ordered
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_counted<F>(&mut self, compare: F) -> usize
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !x.is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_unstable_by(compare);
+        n_ordered
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_key_counted<B, F>(&mut self, mut f: F) -> usize
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let slice = self.as_mut();
+        let ordered = partition_ordered(slice, UnorderedPolicy::Back, false, |x| !f(x).is_outside_order());
+        let n_ordered = ordered.end;
+        slice[ordered].sort_unstable_by(|a, b| f(a).cmp_unwrap(&f(b)));
+        n_ordered
+    }
+
+    #[inline]
+    fn ord_subset_binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+    {
+        self.try_ord_subset_binary_search(x).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+        F: FnMut(&T) -> Ordering,
+    {
+        // unordered always at end, search only in front of them
+        let n_ordered = self.ord_subset_ordered_prefix_len();
+        self.ord_subset_binary_search_in_prefix(n_ordered, f)
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_in_prefix<F>(&self, n_ordered: usize, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        self.as_ref()[..n_ordered].binary_search_by(f)
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_by_key<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.try_ord_subset_binary_search_by_key(b, f).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_by_key_ref<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        B: ?Sized + OrdSubset,
+        F: for<'a> FnMut(&'a T) -> &'a B,
+    {
+        if b.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let n_ordered = partition_point(self.as_ref(), |x| !f(x).is_outside_order());
+        self.ord_subset_binary_search_in_prefix(n_ordered, |k| {
+            f(k).partial_cmp(b).expect(ERROR_BINARY_SEARCH_EXPECT)
+        })
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_by_key_borrowed<Q, B, F>(&self, q: &Q, mut f: F) -> Result<usize, usize>
+    where
+        Q: ?Sized + OrdSubset,
+        B: ?Sized + Borrow<Q>,
+        F: for<'a> FnMut(&'a T) -> &'a B,
+    {
+        if q.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let n_ordered = partition_point(self.as_ref(), |x| !f(x).borrow().is_outside_order());
+        self.ord_subset_binary_search_in_prefix(n_ordered, |k| {
+            f(k).borrow().partial_cmp(q).expect(ERROR_BINARY_SEARCH_EXPECT)
+        })
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_rev(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+    {
+        self.try_ord_subset_binary_search_rev(x).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    #[inline]
+    fn try_ord_subset_binary_search(&self, x: &T) -> Result<Result<usize, usize>, SearchError>
+    where
+        T: OrdSubset,
+    {
+        if x.is_outside_order() {
+            return Err(SearchError::NeedleUnordered);
+        }
+        // unordered always at end, search only in front of them
+        let n_ordered = self.ord_subset_ordered_prefix_len();
+        try_binary_search_in_prefix(self.as_ref(), n_ordered, |other| other.partial_cmp(x))
+    }
+
+    #[inline]
+    fn try_ord_subset_binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<Result<usize, usize>, SearchError>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        if b.is_outside_order() {
+            return Err(SearchError::NeedleUnordered);
+        }
+        // keys outside the order are at the end, search only in front of them
+        let n_ordered = self.ord_subset_ordered_prefix_len_by_key(&mut f);
+        try_binary_search_in_prefix(self.as_ref(), n_ordered, |k| f(k).partial_cmp(b))
+    }
+
+    #[inline]
+    fn try_ord_subset_binary_search_rev(&self, x: &T) -> Result<Result<usize, usize>, SearchError>
+    where
+        T: OrdSubset,
+    {
+        if x.is_outside_order() {
+            return Err(SearchError::NeedleUnordered);
+        }
+        let n_ordered = self.ord_subset_ordered_prefix_len();
+        try_binary_search_in_prefix(self.as_ref(), n_ordered, |other| x.partial_cmp(other))
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_range(&self, x: &T) -> Range<usize>
+    where
+        T: OrdSubset,
+    {
+        if x.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let slice = self.as_ref();
+        let cmp = |other: &T| match other.is_outside_order() {
+            true => Greater, // unordered always at end
+            false => other.partial_cmp(x).expect(ERROR_BINARY_SEARCH_EXPECT),
+        };
+
+        let mut lo = 0;
+        let mut hi = slice.len();
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match cmp(&slice[mid]) {
+                Less => lo = mid + 1,
+                Greater => hi = mid,
+                // found an equal element, the bounds of the range
+                // are on either side of it
+                Equal => {
+                    let start = lo + partition_point(&slice[lo..mid], |e| cmp(e) == Less);
+                    let end = mid + 1
+                        + partition_point(&slice[mid + 1..hi], |e| cmp(e) != Greater);
+                    return start..end;
+                }
+            }
+        }
+        lo..lo
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_first(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+    {
+        if x.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let cmp_ord = |a: &T, b: &T| a.partial_cmp(b).expect(ERROR_BINARY_SEARCH_EXPECT);
+        let cmp = |e: &T| cmp_unordered_greater_all(e, x, cmp_ord);
+        binary_search_edge(self.as_ref(), cmp, false)
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_last(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+    {
+        if x.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let cmp_ord = |a: &T, b: &T| a.partial_cmp(b).expect(ERROR_BINARY_SEARCH_EXPECT);
+        let cmp = |e: &T| cmp_unordered_greater_all(e, x, cmp_ord);
+        binary_search_edge(self.as_ref(), cmp, true)
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_first_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        if b.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let cmp_ord = |a: &B, b: &B| a.partial_cmp(b).expect(ERROR_BINARY_SEARCH_EXPECT);
+        let cmp = |e: &T| cmp_unordered_greater_all(&f(e), b, cmp_ord);
+        binary_search_edge(self.as_ref(), cmp, false)
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_last_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        if b.is_outside_order() {
+            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
+        };
+        let cmp_ord = |a: &B, b: &B| a.partial_cmp(b).expect(ERROR_BINARY_SEARCH_EXPECT);
+        let cmp = |e: &T| cmp_unordered_greater_all(&f(e), b, cmp_ord);
+        binary_search_edge(self.as_ref(), cmp, true)
+    }
+
+    #[inline]
+    fn ord_subset_closest(&self, x: &T) -> Option<(usize, &T)>
+    where
+        T: OrdSubsetFloat + Into<f64>,
+    {
+        // Only called for neighbours strictly below and above `x`, so never `inf - inf`.
+        self.ord_subset_closest_by(x, |&a, &b| {
+            let (a, b): (f64, f64) = (a.into(), b.into());
+            match a > b {
+                true => a - b,
+                false => b - a,
+            }
+        })
+    }
+
+    #[inline]
+    fn ord_subset_closest_by<D, F>(&self, x: &T, mut dist: F) -> Option<(usize, &T)>
+    where
+        T: OrdSubset,
+        D: PartialOrd,
+        F: FnMut(&T, &T) -> D,
+    {
+        if x.is_outside_order() {
+            return None;
+        }
+        let slice = self.as_ref();
+        let n_ordered = self.ord_subset_ordered_prefix_len();
+        let idx = match try_binary_search_in_prefix(slice, n_ordered, |other| other.partial_cmp(x)) {
+            Ok(Ok(idx)) => return Some((idx, &slice[idx])),
+            Ok(Err(idx)) => idx,
+            Err(e) => panic!("{}", e),
+        };
+        let idx = match (idx, idx < n_ordered) {
+            (0, false) => return None,
+            (0, true) => 0,
+            (_, false) => idx - 1,
+            (_, true) => match dist(x, &slice[idx]).partial_cmp(&dist(x, &slice[idx - 1])) {
+                Some(Less) => idx,
+                _ => idx - 1,
+            },
+        };
+        Some((idx, &slice[idx]))
+    }
+
+    #[inline]
+    fn ord_subset_contains(&self, x: &T) -> bool
+    where
+        T: OrdSubset,
+    {
+        !x.is_outside_order() && self.ord_subset_binary_search(x).is_ok()
+    }
+
+    #[inline]
+    fn ord_subset_contains_by_key<B, F>(&self, b: &B, f: F) -> bool
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        !b.is_outside_order() && self.ord_subset_binary_search_by_key(b, f).is_ok()
+    }
+
+    #[inline]
+    fn ord_subset_ordered_prefix_len(&self) -> usize
+    where
+        T: OrdSubset,
+    {
+        partition_point(self.as_ref(), |x| !x.is_outside_order())
+    }
+
+    #[inline]
+    fn ord_subset_ordered_prefix_len_by_key<B, F>(&self, mut f: F) -> usize
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        partition_point(self.as_ref(), |x| !f(x).is_outside_order())
+    }
 
-    /// Binary search a slice sorted in reverse order for a given element. Values outside the ordered subset need to be at the end of the slice.
-    ///
-    /// If a matching value is found then returns Ok, containing the index for the matched element; if no match is found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
-    fn ord_subset_binary_search_rev(&self, x: &T) -> Result<usize, usize>
+    #[inline]
+    fn ord_subset_is_partitioned(&self) -> bool
     where
-        T: OrdSubset;
-}
+        T: OrdSubset,
+    {
+        self.as_ref()
+            .iter()
+            .skip_while(|x| !x.is_outside_order())
+            .all(|x| x.is_outside_order())
+    }
 
-impl<T, U> OrdSubsetSliceExt<T> for U
-where
-    U: AsRef<[T]>,
-{
-    #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_sort(&mut self)
+    fn ord_subset_is_partitioned_by_key<B, F>(&self, mut f: F) -> bool
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.as_ref()
+            .iter()
+            .map(|x| f(x).is_outside_order())
+            .skip_while(|&unordered| !unordered)
+            .all(|unordered| unordered)
+    }
+
+    #[inline]
+    fn ord_subset_partition_unordered(&mut self) -> usize
     where
         U: AsMut<[T]>,
         T: OrdSubset,
     {
-        self.as_mut().ord_subset_sort_by(|a, b| a.cmp_unwrap(b))
+        stable_partition(self.as_mut(), &mut |x: &T| !x.is_outside_order())
+    }
+
+    #[inline]
+    fn ord_subset_partition_unordered_by_key<B, F>(&mut self, mut f: F) -> usize
+    where
+        U: AsMut<[T]>,
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        stable_partition(self.as_mut(), &mut |x: &T| !f(x).is_outside_order())
     }
 
-    #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_sort_by<F>(&mut self, mut compare: F)
+    fn ord_subset_swap_unordered_to_end(&mut self) -> usize
     where
         U: AsMut<[T]>,
         T: OrdSubset,
+    {
+        swap_partition(self.as_mut(), &mut |x: &T| !x.is_outside_order())
+    }
+
+    #[inline]
+    fn ord_subset_max(&self) -> Option<&T>
+    where
+        T: OrdSubset,
+    {
+        self.ord_subset_max_by(|a, b| a.cmp_unwrap(b))
+    }
+
+    #[inline]
+    fn ord_subset_min(&self) -> Option<&T>
+    where
+        T: OrdSubset,
+    {
+        self.ord_subset_min_by(|a, b| a.cmp_unwrap(b))
+    }
+
+    #[inline]
+    fn ord_subset_max_by<F>(&self, mut compare: F) -> Option<&T>
+    where
+        T: OrdSubset,
         F: FnMut(&T, &T) -> Ordering,
     {
-        self.as_mut()
-            .sort_by(|a, b| cmp_unordered_greater_all(a, b, &mut compare))
+        self.as_ref()
+            .iter()
+            .filter(|x| !x.is_outside_order())
+            .max_by(|a, b| compare(a, b))
+    }
+
+    #[inline]
+    fn ord_subset_min_by<F>(&self, mut compare: F) -> Option<&T>
+    where
+        T: OrdSubset,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_ref()
+            .iter()
+            .filter(|x| !x.is_outside_order())
+            .min_by(|a, b| compare(a, b))
+    }
+
+    #[inline]
+    fn ord_subset_max_by_key<B, F>(&self, mut f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.as_ref()
+            .iter()
+            .filter_map(|x| OrdVar::new_checked(f(x)).map(|key| (key, x)))
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, x)| x)
+    }
+
+    #[inline]
+    fn ord_subset_min_by_key<B, F>(&self, mut f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.as_ref()
+            .iter()
+            .filter_map(|x| OrdVar::new_checked(f(x)).map(|key| (key, x)))
+            .min_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, x)| x)
+    }
+
+    #[inline]
+    fn ord_subset_max_index(&self) -> Option<usize>
+    where
+        T: OrdSubset,
+    {
+        self.ord_subset_max_with_index().map(|(idx, _)| idx)
+    }
+
+    #[inline]
+    fn ord_subset_min_index(&self) -> Option<usize>
+    where
+        T: OrdSubset,
+    {
+        self.ord_subset_min_with_index().map(|(idx, _)| idx)
+    }
+
+    #[inline]
+    fn ord_subset_max_with_index(&self) -> Option<(usize, &T)>
+    where
+        T: OrdSubset,
+    {
+        self.as_ref()
+            .iter()
+            .enumerate()
+            .filter(|&(_, x)| !x.is_outside_order())
+            .max_by(|a, b| a.1.cmp_unwrap(b.1))
+    }
+
+    #[inline]
+    fn ord_subset_min_with_index(&self) -> Option<(usize, &T)>
+    where
+        T: OrdSubset,
+    {
+        self.as_ref()
+            .iter()
+            .enumerate()
+            .filter(|&(_, x)| !x.is_outside_order())
+            .min_by(|a, b| a.1.cmp_unwrap(b.1))
     }
 
     #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_sort_rev(&mut self)
+    fn ord_subset_max_set(&self) -> Vec<&T>
     where
-        U: AsMut<[T]>,
         T: OrdSubset,
     {
-        self.as_mut().ord_subset_sort_by(|a, b| b.cmp_unwrap(a))
+        extremum_set(self.as_ref(), Greater)
     }
 
     #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_sort_by_key<B, F>(&mut self, mut f: F)
+    fn ord_subset_min_set(&self) -> Vec<&T>
+    where
+        T: OrdSubset,
+    {
+        extremum_set(self.as_ref(), Less)
+    }
+
+    #[inline]
+    fn ord_subset_max_index_by_key<B, F>(&self, mut f: F) -> Option<usize>
     where
-        U: AsMut<[T]>,
         B: OrdSubset,
         F: FnMut(&T) -> B,
     {
-        self.as_mut()
-            .sort_by(|a, b| cmp_unordered_greater_all(&(f(a)), &(f(b)), CmpUnwrap::cmp_unwrap))
+        self.as_ref()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, x)| OrdVar::new_checked(f(x)).map(|key| (key, idx)))
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, idx)| idx)
     }
 
     #[inline]
-    fn ord_subset_sort_unstable(&mut self)
+    fn ord_subset_min_index_by_key<B, F>(&self, mut f: F) -> Option<usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.as_ref()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, x)| OrdVar::new_checked(f(x)).map(|key| (key, idx)))
+            .min_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, idx)| idx)
+    }
+
+    #[inline]
+    fn ord_subset_extrema_by_key<B, F>(&self, mut f: F) -> Option<(usize, usize)>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        min_max_by_key(
+            self.as_ref()
+                .iter()
+                .enumerate()
+                .map(|(idx, x)| (idx, f(x)))
+                .filter(|(_, key)| !key.is_outside_order()),
+        )
+    }
+
+    #[inline]
+    fn ord_subset_minmax(&self) -> Option<(&T, &T)>
+    where
+        T: OrdSubset,
+    {
+        min_max_by_key(
+            self.as_ref()
+                .iter()
+                .filter(|x| !x.is_outside_order())
+                .map(|x| (x, x)),
+        )
+    }
+
+    #[inline]
+    fn ord_subset_minmax_indices(&self) -> Option<(usize, usize)>
+    where
+        T: OrdSubset,
+    {
+        min_max_by_key(
+            self.as_ref()
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| !x.is_outside_order()),
+        )
+    }
+
+    #[inline]
+    fn ord_subset_minmax_by_key<B, F>(&self, mut f: F) -> Option<(&T, &T)>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        min_max_by_key(
+            self.as_ref()
+                .iter()
+                .map(|x| (x, f(x)))
+                .filter(|(_, key)| !key.is_outside_order()),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_rank(&self) -> Vec<Option<usize>>
+    where
+        T: OrdSubset,
+    {
+        let slice = self.as_ref();
+        let mut indices: Vec<usize> = (0..slice.len())
+            .filter(|&idx| !slice[idx].is_outside_order())
+            .collect();
+        indices.sort_by(|&a, &b| slice[a].cmp_unwrap(&slice[b]));
+
+        let mut ranks = vec![None; slice.len()];
+        let mut rank = 0;
+        for (pos, &idx) in indices.iter().enumerate() {
+            if pos != 0 && slice[indices[pos - 1]].cmp_unwrap(&slice[idx]) != Equal {
+                rank = pos;
+            }
+            ranks[idx] = Some(rank);
+        }
+        ranks
+    }
+
+    #[inline]
+    fn ord_subset_quantile(&self, q: f64) -> Option<&T>
+    where
+        T: OrdSubset,
+    {
+        let slice = self.as_ref();
+        let n_ordered = slice.ord_subset_ordered_prefix_len();
+        // also catches NaN
+        if !(0.0..=1.0).contains(&q) || n_ordered == 0 {
+            return None;
+        }
+        Some(&slice[nearest_rank_index(q * n_ordered as f64)])
+    }
+
+    #[inline]
+    fn ord_subset_percentile(&self, p: f64) -> Option<&T>
+    where
+        T: OrdSubset,
+    {
+        let slice = self.as_ref();
+        let n_ordered = slice.ord_subset_ordered_prefix_len();
+        // also catches NaN
+        if !(0.0..=100.0).contains(&p) || n_ordered == 0 {
+            return None;
+        }
+        // multiply first, `p / 100.0` is rarely exact
+        Some(&slice[nearest_rank_index(p * n_ordered as f64 / 100.0)])
+    }
+
+    #[inline]
+    fn ord_subset_percentile_sorted(&self, p: f64) -> Option<f64>
+    where
+        T: OrdSubsetFloat + Into<f64>,
+    {
+        let slice = self.as_ref();
+        let ordered = &slice[..slice.ord_subset_ordered_prefix_len()];
+        debug_assert!(
+            slice.ord_subset_is_partitioned() && ordered.windows(2).all(|w| w[0] <= w[1]),
+            "ord_subset_percentile_sorted called on unsorted slice"
+        );
+        // also catches NaN
+        if !(0.0..=100.0).contains(&p) || ordered.is_empty() {
+            return None;
+        }
+        let h = (ordered.len() - 1) as f64 * p / 100.0;
+        let lo = h as usize;
+        let frac = h - lo as f64;
+        let a: f64 = ordered[lo].into();
+        let b: f64 = match ordered.get(lo + 1) {
+            Some(&b) if frac != 0.0 => b.into(),
+            _ => return Some(a),
+        };
+        // interpolating towards or away from an infinity would give NaN from inf - inf or 0 * inf
+        match (a.is_finite(), b.is_finite()) {
+            (true, true) => Some(a + frac * (b - a)),
+            (false, _) => Some(a),
+            (true, false) => Some(b),
+        }
+    }
+
+    #[inline]
+    fn ord_subset_nth_smallest(&mut self, n: usize) -> Option<&T>
     where
         U: AsMut<[T]>,
         T: OrdSubset,
     {
-        self.as_mut()
-            .ord_subset_sort_unstable_by(|a, b| a.cmp_unwrap(b))
+        let slice = self.as_mut();
+        let n_ordered = swap_partition(slice, &mut |x: &T| !x.is_outside_order());
+        if n >= n_ordered {
+            return None;
+        }
+        let (_, nth, _) = slice[..n_ordered].select_nth_unstable_by(n, |a, b| a.cmp_unwrap(b));
+        Some(nth)
     }
 
     #[inline]
-    fn ord_subset_sort_unstable_by<F>(&mut self, mut compare: F)
+    fn ord_subset_nth_largest(&mut self, n: usize) -> Option<&T>
     where
         U: AsMut<[T]>,
         T: OrdSubset,
-        F: FnMut(&T, &T) -> Ordering,
     {
-        self.as_mut()
-            .sort_unstable_by(|a, b| cmp_unordered_greater_all(a, b, &mut compare))
+        let slice = self.as_mut();
+        let n_ordered = swap_partition(slice, &mut |x: &T| !x.is_outside_order());
+        if n >= n_ordered {
+            return None;
+        }
+        let (_, nth, _) = slice[..n_ordered].select_nth_unstable_by(n, |a, b| b.cmp_unwrap(a));
+        Some(nth)
     }
 
     #[inline]
-    fn ord_subset_sort_unstable_rev(&mut self)
+    fn ord_subset_median(&mut self) -> Option<&T>
     where
         U: AsMut<[T]>,
         T: OrdSubset,
     {
-        self.as_mut()
-            .ord_subset_sort_unstable_by(|a, b| b.cmp_unwrap(a))
+        let slice = self.as_mut();
+        let n_ordered = swap_partition(slice, &mut |x: &T| !x.is_outside_order());
+        if n_ordered == 0 {
+            return None;
+        }
+        let (_, median, _) =
+            slice[..n_ordered].select_nth_unstable_by((n_ordered - 1) / 2, |a, b| a.cmp_unwrap(b));
+        Some(median)
     }
 
     #[inline]
-    fn ord_subset_sort_unstable_by_key<B, F>(&mut self, mut f: F)
+    fn ord_subset_median_interpolated(&mut self) -> Option<f64>
     where
         U: AsMut<[T]>,
-        B: OrdSubset,
-        F: FnMut(&T) -> B,
+        T: OrdSubsetFloat + Into<f64>,
     {
-        self.as_mut().sort_unstable_by(|a, b| {
-            cmp_unordered_greater_all(&(f(a)), &(f(b)), CmpUnwrap::cmp_unwrap)
-        })
+        let slice = self.as_mut();
+        let n_ordered = swap_partition(slice, &mut |x: &T| !x.is_outside_order());
+        if n_ordered == 0 {
+            return None;
+        }
+        let (lower_half, upper, _) =
+            slice[..n_ordered].select_nth_unstable_by(n_ordered / 2, |a, b| a.cmp_unwrap(b));
+        let upper: f64 = (*upper).into();
+        match n_ordered % 2 {
+            1 => Some(upper),
+            _ => {
+                // the lower middle value is the maximum of the lower half
+                let lower: f64 = (*lower_half.ord_subset_max()?).into();
+                // the mean of -inf and inf is undefined
+                if lower.is_infinite() && upper.is_infinite() && lower != upper {
+                    return None;
+                }
+                // halving first can't overflow
+                Some(lower / 2.0 + upper / 2.0)
+            }
+        }
     }
 
+    #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_binary_search(&self, x: &T) -> Result<usize, usize>
+    fn ord_subset_quantiles(&mut self, qs: &[f64]) -> Vec<Option<T>>
+    where
+        U: AsMut<[T]>,
+        T: OrdSubset + Clone,
+    {
+        if let Some(q) = qs.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+            panic!("{}: {}", ERROR_QUANTILE_OUT_OF_RANGE, q)
+        }
+        self.as_mut().ord_subset_sort_unstable();
+        qs.iter()
+            .map(|&q| self.ord_subset_quantile(q).cloned())
+            .collect()
+    }
+
+    #[inline]
+    fn ord_subset_chunk_by<'a>(&'a self) -> ChunkBy<'a, T>
     where
         T: OrdSubset,
     {
-        if x.is_outside_order() {
-            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
-        };
-        self.ord_subset_binary_search_by(|other| {
-            other.partial_cmp(x).expect(ERROR_BINARY_SEARCH_EXPECT)
-        })
+        ChunkBy {
+            slice: self.as_ref(),
+        }
     }
 
+    #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    fn ord_subset_sliding_max(&self, w: usize) -> Vec<Option<&T>>
     where
         T: OrdSubset,
-        F: FnMut(&T) -> Ordering,
     {
-        self.as_ref().binary_search_by(|other| {
-            match other.is_outside_order() {
-                true => Greater, // unordered always at end
-                false => f(other),
-            }
-        })
+        sliding_extremum(self.as_ref(), w, |new, old| new.cmp_unwrap(old) != Less)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_sliding_min(&self, w: usize) -> Vec<Option<&T>>
+    where
+        T: OrdSubset,
+    {
+        sliding_extremum(self.as_ref(), w, |new, old| new.cmp_unwrap(old) == Less)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn ord_subset_merge(&self, other: &[T]) -> Vec<T>
+    where
+        T: OrdSubset + Clone,
+    {
+        let mut out = Vec::new();
+        self.ord_subset_merge_into(other, &mut out);
+        out
     }
 
+    #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    fn ord_subset_merge_by_key<B, F>(&self, other: &[T], mut f: F) -> Vec<T>
     where
+        T: Clone,
         B: OrdSubset,
         F: FnMut(&T) -> B,
     {
-        if b.is_outside_order() {
-            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
-        };
-        // compare ordered values as expected
-        // wrap it in a function that deals with unordered, so this one never sees them
-        let cmp_ord = |a: &B, b: &B| a.partial_cmp(b).expect(ERROR_BINARY_SEARCH_EXPECT);
-        self.as_ref()
-            .binary_search_by(|k| cmp_unordered_greater_all(&f(k), b, &cmp_ord))
+        let slice = self.as_ref();
+        let slice_len = slice.ord_subset_ordered_prefix_len_by_key(&mut f);
+        let other_len = other.ord_subset_ordered_prefix_len_by_key(&mut f);
+        let mut out = Vec::new();
+        merge_into(slice, slice_len, other, other_len, |a, b| f(a).cmp_unwrap(&f(b)), &mut out);
+        out
     }
 
+    #[cfg(feature = "std")]
     #[inline]
-    fn ord_subset_binary_search_rev(&self, x: &T) -> Result<usize, usize>
+    fn ord_subset_merge_into(&self, other: &[T], out: &mut Vec<T>)
     where
-        T: OrdSubset,
+        T: OrdSubset + Clone,
     {
-        if x.is_outside_order() {
-            panic!(ERROR_BINARY_SEARCH_OUTSIDE_ORDER)
-        };
-        self.ord_subset_binary_search_by(|other| {
-            x.partial_cmp(other).expect(ERROR_BINARY_SEARCH_EXPECT)
-        })
+        let slice = self.as_ref();
+        let slice_len = slice.ord_subset_ordered_prefix_len();
+        let other_len = other.ord_subset_ordered_prefix_len();
+        merge_into(slice, slice_len, other, other_len, |a, b| a.cmp_unwrap(b), out)
+    }
+}
+
+/// An iterator over runs of equal elements of a slice.
+///
+/// This struct is created by the `ord_subset_chunk_by` method on `OrdSubsetSliceExt`.
+#[derive(Clone, Debug)]
+pub struct ChunkBy<'a, T: 'a> {
+    slice: &'a [T],
+}
+
+impl<'a, T: OrdSubset> Iterator for ChunkBy<'a, T> {
+    type Item = &'a [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.slice.first()?;
+        let first_is_unordered = first.is_outside_order();
+        let run_len = 1 + self.slice[1..]
+            .iter()
+            .take_while(|x| match (first_is_unordered, x.is_outside_order()) {
+                (true, true) => true,
+                (false, false) => first.cmp_unwrap(x) == Equal,
+                _ => false,
+            })
+            .count();
+        let (chunk, rest) = self.slice.split_at(run_len);
+        self.slice = rest;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.slice.len() {
+            0 => (0, Some(0)),
+            len => (1, Some(len)),
+        }
     }
 }
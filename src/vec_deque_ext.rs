@@ -0,0 +1,207 @@
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0 or the MIT license
+// http://opensource.org/licenses/MIT, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ord_subset_trait::*;
+use iter_ext::OrdSubsetIterExt;
+use slice_ext::OrdSubsetSliceExt;
+use core::cmp::Ordering::Greater;
+use std::collections::VecDeque;
+
+// Shifts the result of a binary search in the back slice of a deque to an index of the whole deque
+#[inline]
+fn offset(result: Result<usize, usize>, n: usize) -> Result<usize, usize> {
+    match result {
+        Ok(idx) => Ok(idx + n),
+        Err(idx) => Err(idx + n),
+    }
+}
+
+/// Extension trait for `VecDeque`. Mirrors the methods of `OrdSubsetSliceExt`.
+///
+/// Sorts rearrange the deque into a single contiguous slice with `.make_contiguous()`.
+/// All other methods work on deques that wrap around their buffer without moving any elements.
+pub trait OrdSubsetVecDequeExt<T> {
+    /// Sort the deque. Values outside the ordered subset are put at the end.
+    ///
+    /// The deque is made contiguous first. See `OrdSubsetSliceExt::ord_subset_sort_unstable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_sort_unstable(&mut self)
+    where
+        T: OrdSubset;
+
+    /// Sorts the deque, using `key` to extract a key by which to order the sort by. Entries mapping to values outside
+    /// the total order will be put at the end.
+    ///
+    /// The deque is made contiguous first. See `OrdSubsetSliceExt::ord_subset_sort_unstable_by_key`.
+    fn ord_subset_sort_unstable_by_key<B, F>(&mut self, f: F)
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Binary search a sorted deque for a given element. Values outside the ordered subset need to be at the end of the deque.
+    /// The deque does not need to be contiguous.
+    ///
+    /// If a matching value is found then returns Ok, containing the index for the matched element; if no match is found then Err is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetVecDequeExt;
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut deque: VecDeque<_> = vec![3.0, 4.0, std::f64::NAN].into();
+    /// deque.push_front(2.0);
+    /// deque.push_front(1.0);
+    /// assert_eq!(deque.ord_subset_binary_search(&3.0), Ok(2));
+    /// assert_eq!(deque.ord_subset_binary_search(&5.0), Err(4));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is outside of the total order. Also panics when `a.partial_cmp(b)` returns `None` for two values `a`,`b` inside the total order (Violated OrdSubset contract).
+    fn ord_subset_binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset;
+
+    /// Binary search a sorted deque with a key extraction function.
+    /// The deque does not need to be contiguous.
+    ///
+    /// Assumes that the deque is sorted by the key, for instance with `ord_subset_sort_unstable_by_key` using the same key extraction function.
+    ///
+    /// If a matching value is found then returns `Ok`, containing the index for the matched element; if no match is found then `Err` is returned, containing the index where a matching element could be inserted while maintaining sorted order.
+    fn ord_subset_binary_search_by_key<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the maximum element of the deque. Values outside the ordered subset are ignored.
+    ///
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    fn ord_subset_max(&self) -> Option<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the minimum element of the deque. Values outside the ordered subset are ignored.
+    ///
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    fn ord_subset_min(&self) -> Option<&T>
+    where
+        T: OrdSubset;
+
+    /// Returns the element that gives the maximum value from the specified function.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// Returns the last element if the comparison determines multiple elements to be equally maximum.
+    fn ord_subset_max_by_key<B, F>(&self, f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Returns the element that gives the minimum value from the specified function.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// Returns the first element if the comparison determines multiple elements to be equally minimum.
+    fn ord_subset_min_by_key<B, F>(&self, f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+}
+
+impl<T> OrdSubsetVecDequeExt<T> for VecDeque<T> {
+    #[inline]
+    fn ord_subset_sort_unstable(&mut self)
+    where
+        T: OrdSubset,
+    {
+        self.make_contiguous().ord_subset_sort_unstable()
+    }
+
+    #[inline]
+    fn ord_subset_sort_unstable_by_key<B, F>(&mut self, f: F)
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.make_contiguous().ord_subset_sort_unstable_by_key(f)
+    }
+
+    #[inline]
+    fn ord_subset_binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: OrdSubset,
+    {
+        let (front, back) = self.as_slices();
+        // all elements of the front are <= the first element of the back
+        // if that is <= x, the search can be restricted to the back
+        let in_back = match back.first() {
+            Some(first) => {
+                !x.is_outside_order() && !first.is_outside_order() && first.cmp_unwrap(x) != Greater
+            }
+            None => false,
+        };
+        match in_back {
+            true => offset(back.ord_subset_binary_search(x), front.len()),
+            false => front.ord_subset_binary_search(x),
+        }
+    }
+
+    #[inline]
+    fn ord_subset_binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let (front, back) = self.as_slices();
+        let in_back = match back.first() {
+            Some(first) => {
+                let key = f(first);
+                !b.is_outside_order() && !key.is_outside_order() && key.cmp_unwrap(b) != Greater
+            }
+            None => false,
+        };
+        match in_back {
+            true => offset(back.ord_subset_binary_search_by_key(b, f), front.len()),
+            false => front.ord_subset_binary_search_by_key(b, f),
+        }
+    }
+
+    #[inline]
+    fn ord_subset_max(&self) -> Option<&T>
+    where
+        T: OrdSubset,
+    {
+        self.iter().ord_subset_max()
+    }
+
+    #[inline]
+    fn ord_subset_min(&self) -> Option<&T>
+    where
+        T: OrdSubset,
+    {
+        self.iter().ord_subset_min()
+    }
+
+    #[inline]
+    fn ord_subset_max_by_key<B, F>(&self, mut f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.iter().ord_subset_max_by_key(|x| f(x))
+    }
+
+    #[inline]
+    fn ord_subset_min_by_key<B, F>(&self, mut f: F) -> Option<&T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.iter().ord_subset_min_by_key(|x| f(x))
+    }
+}
@@ -0,0 +1,315 @@
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0 or the MIT license
+// http://opensource.org/licenses/MIT, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ord_subset_trait::*;
+use slice_ext::{partition_point, OrdSubsetSliceExt};
+use std::vec::Drain;
+use core::cmp::Ordering::{Equal, Greater};
+
+// Two values are duplicates if they compare equal or if both are outside the total order.
+#[inline]
+fn is_duplicate<B: OrdSubset>(a: &B, b: &B) -> bool {
+    match (a.is_outside_order(), b.is_outside_order()) {
+        (false, false) => a.partial_cmp(b) == Some(Equal),
+        (true, true) => true,
+        _ => false,
+    }
+}
+
+/// Extension trait for methods on `Vec` that need to remove or insert elements.
+pub trait OrdSubsetVecExt<T> {
+    /// Removes consecutive repeated elements. Elements are repeated if they compare `Equal`.
+    /// Consecutive values outside the total order are treated as repeated, too, and collapsed into the first of them.
+    ///
+    /// If the vector is sorted with any of the `ord_subset_sort` methods, this removes all duplicates
+    /// and leaves at most one value outside the total order at the end. If you want to drop those completely, check the last element afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::{OrdSubsetSliceExt, OrdSubsetVecExt};
+    ///
+    /// let mut v = vec![3.0, 1.0, std::f64::NAN, 1.0, std::f64::NAN];
+    /// v.ord_subset_sort();
+    /// v.ord_subset_dedup();
+    /// assert_eq!(&v[..2], &[1.0, 3.0]);
+    /// assert!(v[2].is_nan());
+    /// assert_eq!(v.len(), 3);
+    /// ```
+    fn ord_subset_dedup(&mut self)
+    where
+        T: OrdSubset;
+
+    /// Removes consecutive elements whose keys compare `Equal`.
+    /// Consecutive elements with keys outside the total order are treated as repeated, too, and collapsed into the first of them.
+    ///
+    /// The key function is called twice per comparison.
+    fn ord_subset_dedup_by_key<B, F>(&mut self, f: F)
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Removes all values outside the ordered subset. The remaining elements keep their order.
+    /// Returns the number of removed elements.
+    ///
+    /// This delegates to `.retain()`, which shifts the remaining elements down in a single pass.
+    /// It takes O(n) time and does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetVecExt;
+    ///
+    /// let mut v = vec![3.0, std::f64::NAN, 1.0, std::f64::NAN];
+    /// assert_eq!(v.ord_subset_retain_ordered(), 2);
+    /// assert_eq!(v, [3.0, 1.0]);
+    /// ```
+    fn ord_subset_retain_ordered(&mut self) -> usize
+    where
+        T: OrdSubset;
+
+    /// Removes all elements whose key is outside the ordered subset. The remaining elements keep their order.
+    /// Returns the number of removed elements. The key function is called exactly once per element.
+    fn ord_subset_retain_ordered_by_key<B, F>(&mut self, f: F) -> usize
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Inserts `x` into a vector sorted with `ord_subset_sort` and returns the index it was inserted at.
+    /// If `x` is equal to existing elements, it is inserted after them.
+    /// If `x` is outside the total order, it is pushed to the end.
+    ///
+    /// The insertion point is found with a binary search. If the vector isn't sorted, the position is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetVecExt;
+    ///
+    /// let mut v = vec![1.0, 3.0, std::f64::NAN];
+    /// assert_eq!(v.ord_subset_insert_sorted(2.0), 1);
+    /// assert_eq!(v.ord_subset_insert_sorted(std::f64::NAN), 4);
+    /// assert_eq!(&v[..3], &[1.0, 2.0, 3.0]);
+    /// ```
+    fn ord_subset_insert_sorted(&mut self, x: T) -> usize
+    where
+        T: OrdSubset;
+
+    /// Inserts `x` into a vector sorted with `ord_subset_sort_by_key` using the same key function and returns the index it was inserted at.
+    /// If the key of `x` is equal to existing keys, it is inserted after them.
+    /// If the key of `x` is outside the total order, it is pushed to the end.
+    fn ord_subset_insert_sorted_by_key<B, F>(&mut self, x: T, f: F) -> usize
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Removes all values outside the ordered subset and returns them as an iterator in their original order.
+    /// The remaining elements keep their order.
+    ///
+    /// The elements are moved to the end with `ord_subset_partition_unordered()` first and then removed with `.drain()`.
+    /// Like with `.drain()`, all values outside the total order are removed, even if the iterator is dropped before being fully consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetVecExt;
+    ///
+    /// let mut v = vec![3.0, std::f64::NAN, 1.0, std::f64::NAN];
+    /// assert_eq!(v.ord_subset_drain_unordered().count(), 2);
+    /// assert_eq!(v, [3.0, 1.0]);
+    /// ```
+    fn ord_subset_drain_unordered<'a>(&'a mut self) -> Drain<'a, T>
+    where
+        T: OrdSubset;
+
+    /// Removes all elements whose key is outside the ordered subset and returns them as an iterator in their original order.
+    /// The remaining elements keep their order. The key function is called exactly once per element.
+    ///
+    /// Like with `.drain()`, all elements with keys outside the total order are removed, even if the iterator is dropped before being fully consumed.
+    fn ord_subset_drain_unordered_by_key<'a, B, F>(&'a mut self, f: F) -> Drain<'a, T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Removes the maximum element and returns it. Values outside the ordered subset are ignored.
+    /// Returns `None` if the vector contains no values inside the total order.
+    ///
+    /// The element is removed with `.swap_remove()`, so the last element takes its place. This takes O(n) time.
+    /// If multiple elements are equally maximum, the last one is removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ord_subset::OrdSubsetVecExt;
+    ///
+    /// let mut v = vec![2.0, 5.0, std::f64::NAN, 3.0];
+    /// assert_eq!(v.ord_subset_pop_max(), Some(5.0));
+    /// assert_eq!(v.ord_subset_pop_max(), Some(3.0));
+    /// assert_eq!(v.ord_subset_pop_max(), Some(2.0));
+    /// assert_eq!(v.ord_subset_pop_max(), None);
+    /// assert_eq!(v.len(), 1);
+    /// ```
+    fn ord_subset_pop_max(&mut self) -> Option<T>
+    where
+        T: OrdSubset;
+
+    /// Removes the minimum element and returns it. Values outside the ordered subset are ignored.
+    /// Returns `None` if the vector contains no values inside the total order.
+    ///
+    /// The element is removed with `.swap_remove()`, so the last element takes its place. This takes O(n) time.
+    /// If multiple elements are equally minimum, the first one is removed.
+    fn ord_subset_pop_min(&mut self) -> Option<T>
+    where
+        T: OrdSubset;
+
+    /// Removes the element that gives the maximum value from the specified function and returns it.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// The element is removed with `.swap_remove()`, so the last element takes its place.
+    /// If multiple elements are equally maximum, the last one is removed.
+    fn ord_subset_pop_max_by_key<B, F>(&mut self, f: F) -> Option<T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+
+    /// Removes the element that gives the minimum value from the specified function and returns it.
+    /// Elements mapping to values outside the ordered subset are ignored.
+    ///
+    /// The element is removed with `.swap_remove()`, so the last element takes its place.
+    /// If multiple elements are equally minimum, the first one is removed.
+    fn ord_subset_pop_min_by_key<B, F>(&mut self, f: F) -> Option<T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B;
+}
+
+impl<T> OrdSubsetVecExt<T> for Vec<T> {
+    #[inline]
+    fn ord_subset_dedup(&mut self)
+    where
+        T: OrdSubset,
+    {
+        self.dedup_by(|a, b| is_duplicate(a, b))
+    }
+
+    #[inline]
+    fn ord_subset_dedup_by_key<B, F>(&mut self, mut f: F)
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        self.dedup_by(|a, b| is_duplicate(&f(a), &f(b)))
+    }
+
+    #[inline]
+    fn ord_subset_retain_ordered(&mut self) -> usize
+    where
+        T: OrdSubset,
+    {
+        let len = self.len();
+        self.retain(|x| !x.is_outside_order());
+        len - self.len()
+    }
+
+    #[inline]
+    fn ord_subset_retain_ordered_by_key<B, F>(&mut self, mut f: F) -> usize
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let len = self.len();
+        self.retain(|x| !f(x).is_outside_order());
+        len - self.len()
+    }
+
+    #[inline]
+    fn ord_subset_insert_sorted(&mut self, x: T) -> usize
+    where
+        T: OrdSubset,
+    {
+        let idx = match x.is_outside_order() {
+            true => self.len(),
+            false => partition_point(self, |e| !e.is_outside_order() && e.cmp_unwrap(&x) != Greater),
+        };
+        self.insert(idx, x);
+        idx
+    }
+
+    #[inline]
+    fn ord_subset_insert_sorted_by_key<B, F>(&mut self, x: T, mut f: F) -> usize
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let key = f(&x);
+        let idx = match key.is_outside_order() {
+            true => self.len(),
+            false => partition_point(self, |e| {
+                let e_key = f(e);
+                !e_key.is_outside_order() && e_key.cmp_unwrap(&key) != Greater
+            }),
+        };
+        self.insert(idx, x);
+        idx
+    }
+
+    #[inline]
+    fn ord_subset_drain_unordered<'a>(&'a mut self) -> Drain<'a, T>
+    where
+        T: OrdSubset,
+    {
+        let n_ordered = self.ord_subset_partition_unordered();
+        self.drain(n_ordered..)
+    }
+
+    #[inline]
+    fn ord_subset_drain_unordered_by_key<'a, B, F>(&'a mut self, f: F) -> Drain<'a, T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let n_ordered = self.ord_subset_partition_unordered_by_key(f);
+        self.drain(n_ordered..)
+    }
+
+    #[inline]
+    fn ord_subset_pop_max(&mut self) -> Option<T>
+    where
+        T: OrdSubset,
+    {
+        let idx = self.ord_subset_max_index()?;
+        Some(self.swap_remove(idx))
+    }
+
+    #[inline]
+    fn ord_subset_pop_min(&mut self) -> Option<T>
+    where
+        T: OrdSubset,
+    {
+        let idx = self.ord_subset_min_index()?;
+        Some(self.swap_remove(idx))
+    }
+
+    #[inline]
+    fn ord_subset_pop_max_by_key<B, F>(&mut self, f: F) -> Option<T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let idx = self.ord_subset_max_index_by_key(f)?;
+        Some(self.swap_remove(idx))
+    }
+
+    #[inline]
+    fn ord_subset_pop_min_by_key<B, F>(&mut self, f: F) -> Option<T>
+    where
+        B: OrdSubset,
+        F: FnMut(&T) -> B,
+    {
+        let idx = self.ord_subset_min_index_by_key(f)?;
+        Some(self.swap_remove(idx))
+    }
+}
@@ -1,10 +1,27 @@
 #![cfg_attr(feature = "cargo-clippy", allow(float_cmp, match_wild_err_arm))]
 extern crate ord_subset;
 extern crate core;
+#[cfg(feature="bytemuck")]
+extern crate bytemuck;
+#[cfg(feature="rust_decimal")]
+extern crate rust_decimal;
+#[cfg(feature="serde")]
+extern crate serde;
+#[cfg(feature="rayon")]
+extern crate rayon;
 use ord_subset::OrdSubsetIterExt;
 use ord_subset::OrdSubsetSliceExt;
 use ord_subset::OrdSubset;
 use ord_subset::OrdVar;
+use ord_subset::{FiniteF32, FiniteF64};
+use ord_subset::SearchError;
+use ord_subset::UnorderedPolicy;
+#[cfg(feature="std")]
+use ord_subset::OrdSubsetVecExt;
+#[cfg(feature="std")]
+use ord_subset::OrdSubsetVecDequeExt;
+#[cfg(feature="rayon")]
+use ord_subset::OrdSubsetParIterExt;
 
 use std::f64::INFINITY as INF;
 use std::f64::NAN;
@@ -25,227 +42,1696 @@ const TEST_ARRAY_NO_NAN: [f64; N_NO_NAN] =
 	16.0,  8.0,  -INF,  4.0,  2.0, 22.0, 18.0, 21.0,
 	15.0,  6.0,  24.0, 19.0, 12.0, 11.0,  5.0, 23.0];
 
-const SORTED_TEST_ARRAY: [f64; N] =
-	[-INF, 0.0,  1.0,  2.0,  3.0,  4.0,  5.0,  6.0,
-	 7.0,  8.0,  9.0, 10.0, 11.0, 12.0, 13.0, 14.0,
-	15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0,
-	23.0, 24.0, 25.0, 26.0, 27.0,  INF,  NAN,  NAN];
+const SORTED_TEST_ARRAY: [f64; N] =
+	[-INF, 0.0,  1.0,  2.0,  3.0,  4.0,  5.0,  6.0,
+	 7.0,  8.0,  9.0, 10.0, 11.0, 12.0, 13.0, 14.0,
+	15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0,
+	23.0, 24.0, 25.0, 26.0, 27.0,  INF,  NAN,  NAN];
+
+const SORTED_TEST_ARRAY_NO_NAN: [f64; N_NO_NAN] =
+	[-INF, 0.0,  1.0,  2.0,  3.0,  4.0,  5.0,  6.0,
+	 7.0,  8.0,  9.0, 10.0, 11.0, 12.0, 13.0, 14.0,
+	15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0,
+	23.0, 24.0, 25.0, 26.0, 27.0, INF             ];
+
+
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+struct NotOrdSub();
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+struct OrdSub();
+
+impl OrdSubset for OrdSub {
+	fn is_outside_order(&self) -> bool {
+		true
+	}
+}
+
+// ---------------------------- iter ext methods -------------------------------
+
+#[test]
+fn ord_subset_max() {
+	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
+	let max = arr.iter().ord_subset_max().unwrap();
+	assert_eq!(&5.0, max);
+}
+
+#[test]
+fn ord_subset_max_by() {
+	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
+	let max_by = arr.iter().ord_subset_max_by_key(|num| num.recip()).unwrap();
+	assert_eq!(&2.0, max_by);
+}
+
+#[test]
+fn ord_subset_min() {
+	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
+	let min = arr.iter().ord_subset_min().unwrap();
+	assert_eq!(&2.0, min);
+}
+
+#[test]
+fn ord_subset_min_by() {
+	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
+	let min_by = arr.iter().ord_subset_min_by_key(|num| num.recip()).unwrap();
+	assert_eq!(&5.0, min_by);
+}
+
+#[test]
+fn ord_subset_min_max_by_key_with_key() {
+	let arr = [(1, 2.0), (2, NAN), (3, 5.0), (4, 5.0), (5, 2.0)];
+	let mut n_calls = 0;
+	let max = arr.iter().ord_subset_max_by_key_with_key(|&&(_, k)| { n_calls += 1; k });
+	assert_eq!(max, Some((&(4, 5.0), 5.0)));
+	assert_eq!(n_calls, arr.len());
+	assert_eq!(arr.iter().ord_subset_min_by_key_with_key(|x| x.1 * 2.0), Some((&(1, 2.0), 4.0)));
+	assert_eq!(arr.iter().ord_subset_min_by_key_with_key(|_| NAN), None);
+}
+
+#[test]
+fn ord_subset_dedup_by_key() {
+	let arr = [1.0, 1.5, NAN, NAN, 1.9, 2.0, 2.5, NAN, 3.0];
+	let dedup: Vec<_> = arr.iter().ord_subset_dedup_by_key(|num| num.floor()).collect();
+	assert_eq!(dedup.len(), 7);
+	assert_eq!(dedup[0], &1.0);
+	assert!(dedup[1].is_nan() && dedup[2].is_nan());
+	assert_eq!(dedup[3], &1.9);
+	assert_eq!(dedup[4], &2.0);
+	assert!(dedup[5].is_nan());
+	assert_eq!(dedup[6], &3.0);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn ord_subset_group_by_key() {
+	let arr = [(1, 0.4), (2, NAN), (3, 2.2), (4, 0.9), (5, -0.5), (6, 2.9)];
+	let groups = arr.iter().ord_subset_group_by_key(|x| x.1.round());
+	let keys: Vec<f64> = groups.keys().map(|k| **k).collect();
+	assert_eq!(keys, [-1.0, 0.0, 1.0, 2.0, 3.0]);
+	assert_eq!(groups[&OrdVar::new(0.0)], [&(1, 0.4)]);
+	assert_eq!(groups[&OrdVar::new(2.0)], [&(3, 2.2)]);
+	// NaN key dropped
+	assert_eq!(groups.values().map(Vec::len).sum::<usize>(), arr.len() - 1);
+
+	let groups = arr.iter().map(|x| x.0).ord_subset_group_by_key(|id| id % 2);
+	assert_eq!(groups[&OrdVar::new(1)], [1, 3, 5]);
+	assert!([NAN].iter().ord_subset_group_by_key(|&&x| x).is_empty());
+}
+
+#[test]
+fn ord_subset_max_min_fn() {
+	assert_eq!(ord_subset::ord_subset_max(&TEST_ARRAY), Some(&INF));
+	assert_eq!(ord_subset::ord_subset_min(TEST_ARRAY.to_vec()), Some(TEST_ARRAY[18]));
+	assert_eq!(ord_subset::ord_subset_max(Vec::<f64>::new()), None);
+
+	use std::collections::HashMap;
+	let map: HashMap<_, _> = [("a", 1.0), ("b", NAN), ("c", -1.0)].iter().cloned().collect();
+	assert_eq!(ord_subset::ord_subset_max(map.values()), Some(&1.0));
+	assert_eq!(ord_subset::ord_subset_min(map.values()), Some(&-1.0));
+}
+
+// This is a compile time test. It can't fail at runtime.
+// The referenced functions must accept iters of values, that are not OrdSubset
+// if the closure produces OrdSubset values
+#[allow(unused)]
+fn ord_subset_min_or_max_by_key() {
+	let array: [NotOrdSub; 0] = [];
+	array.iter().ord_subset_min_by_key(|_| 0.0);
+	array.iter().ord_subset_max_by_key(|_| 0.0);
+}
+
+#[test]
+#[cfg(feature="rayon")]
+fn par_iter_extrema() {
+	use rayon::prelude::*;
+	// unique extrema, NaN in every chunk
+	let vec: Vec<f64> = (0..100_000u64)
+		.map(|i| match i % 97 {
+			0 => NAN,
+			_ => ((i * 7919) % 100_003) as f64,
+		})
+		.collect();
+	assert_eq!(vec.par_iter().ord_subset_max(), vec.iter().ord_subset_max());
+	assert_eq!(vec.par_iter().ord_subset_min(), vec.iter().ord_subset_min());
+	let key = |x: &&f64| -**x;
+	assert_eq!(vec.par_iter().ord_subset_max_by_key(key), vec.iter().ord_subset_max_by_key(key));
+	assert_eq!(vec.par_iter().ord_subset_min_by_key(key), vec.iter().ord_subset_min_by_key(key));
+
+	let nans = vec![NAN; 1000];
+	assert_eq!(nans.par_iter().ord_subset_max(), None);
+	assert_eq!(nans.par_iter().ord_subset_min_by_key(|x| **x), None);
+}
+
+// ---------------------------slice ext methods --------------------------------
+// ----------------------------- stable sorts ----------------------------------
+
+#[test]
+#[cfg(feature="std")]
+fn sort() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort();
+	assert_eq!(&array[0..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_rev() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_rev();
+
+	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
+	rev_sorted_array.reverse();
+
+	assert_eq!(&array[0..N_NO_NAN], &rev_sorted_array);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_rev_by() {
+	// compare only by integer part, stable sort keeps original order for ties
+	let mut array = [(1, 2.5), (2, NAN), (3, 2.0), (4, 7.0), (5, 2.9), (6, NAN)];
+	array.ord_subset_sort_rev_by(|a, b| a.1.floor().partial_cmp(&b.1.floor()).unwrap());
+	let order: Vec<_> = array.iter().map(|&(i, _)| i).collect();
+	assert_eq!(order, [4, 1, 3, 5, 2, 6]);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_by_key() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_by_key(key_function);
+	let mut std_sorted_array = TEST_ARRAY_NO_NAN;
+	std_sorted_array.sort_by_key(|num| OrdVar::new(key_function(num)));
+	assert_eq!(&array[..N_NO_NAN], &std_sorted_array);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_rev_by_key() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_rev_by_key(key_function);
+	let mut std_sorted_array = TEST_ARRAY_NO_NAN;
+	std_sorted_array.sort_by_key(|num| std::cmp::Reverse(OrdVar::new(key_function(num))));
+	assert_eq!(&array[..N_NO_NAN], &std_sorted_array);
+	assert!(array[N_NO_NAN..].iter().all(|num| num.is_nan()));
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_stability() {
+	use ord_subset::ord_subset_cmp;
+	// many ties and unordered values, tagged with their original position
+	let array: Vec<(usize, f64)> = (0..200)
+		.map(|i| match i % 7 {
+			0 | 3 => (i, NAN),
+			_ => (i, (i % 5) as f64),
+		})
+		.collect();
+
+	// std's stable sort with the comparator treating unordered values as equal and greatest
+	let mut expected = array.clone();
+	expected.sort_by(|a, b| ord_subset_cmp(&a.1, &b.1));
+	let tags = |v: &[(usize, f64)]| v.iter().map(|&(i, _)| i).collect::<Vec<_>>();
+
+	let mut sorted = array.clone();
+	sorted.ord_subset_sort_by_key(|x| x.1);
+	assert_eq!(tags(&sorted), tags(&expected));
+
+	let mut sorted = array.clone();
+	sorted.ord_subset_sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+	assert_eq!(tags(&sorted), tags(&expected));
+
+	// unordered values in front keep their order, too
+	let (mut expected, mut ordered): (Vec<_>, Vec<_>) = array.iter().cloned().partition(|x| x.1.is_nan());
+	ordered.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+	expected.extend(ordered);
+	let mut sorted = array.clone();
+	sorted.ord_subset_sort_by_key_with(|x| x.1, UnorderedPolicy::Front);
+	assert_eq!(tags(&sorted), tags(&expected));
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_non_zero() {
+	use std::num::NonZeroU64;
+	let mut ids: Vec<_> = [3, 1, 2].iter().map(|&n| NonZeroU64::new(n).unwrap()).collect();
+	ids.ord_subset_sort();
+	assert_eq!(ids.iter().map(|id| id.get()).collect::<Vec<_>>(), [1, 2, 3]);
+}
+
+#[test]
+fn sort_finite() {
+	let mut array = TEST_ARRAY.map(FiniteF64);
+	array.ord_subset_sort_unstable();
+	let n_finite = N_NO_NAN - 2;
+	assert_eq!(array.ord_subset_ordered_prefix_len(), n_finite);
+	assert!(array[..n_finite].windows(2).all(|w| w[0] <= w[1]));
+	assert!(array[n_finite..].iter().all(|x| !x.0.is_finite()));
+	assert_eq!(array.ord_subset_max(), array[..n_finite].last());
+	assert_eq!(array.try_ord_subset_binary_search(&FiniteF64(INF)), Err(SearchError::NeedleUnordered));
+
+	assert!(FiniteF32(std::f32::NEG_INFINITY).is_outside_order());
+	assert!(!FiniteF32::from(std::f32::MAX).is_outside_order());
+	assert_eq!(f64::from(FiniteF64(1.5)), 1.5);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_by_key_ref() {
+	struct Record {
+		name: String,
+		score: f64,
+	}
+	let record = |name: &str, score| Record { name: name.to_string(), score };
+	let mut records = vec![record("c", NAN), record("a", 2.0), record("b", 1.0), record("a", 0.5)];
+
+	records.ord_subset_sort_by_key_ref(|r| &r.name);
+	let names: Vec<_> = records.iter().map(|r| &r.name[..]).collect();
+	assert_eq!(names, ["a", "a", "b", "c"]);
+	assert_eq!(records[0].score, 2.0); // stable
+	assert_eq!(records.ord_subset_binary_search_by_key_ref("b", |r| &r.name[..]), Ok(2));
+	assert_eq!(records.ord_subset_binary_search_by_key_ref("d", |r| &r.name[..]), Err(4));
+
+	records.ord_subset_sort_unstable_by_key_ref(|r| &r.score);
+	let scores: Vec<_> = records.iter().map(|r| r.score).take(3).collect();
+	assert_eq!(scores, [0.5, 1.0, 2.0]);
+	assert!(records[3].score.is_nan());
+	assert_eq!(records.ord_subset_binary_search_by_key_ref(&1.0, |r| &r.score), Ok(1));
+	assert_eq!(records.ord_subset_binary_search_by_key_ref(&3.0, |r| &r.score), Err(3));
+}
+
+#[test]
+#[cfg(feature="std")]
+fn to_sorted_vec() {
+	let array = TEST_ARRAY;
+	let mut sorted = TEST_ARRAY;
+	sorted.ord_subset_sort();
+	let copy = array.to_ord_subset_sorted_vec();
+	assert_eq!(copy[..N_NO_NAN], sorted[..N_NO_NAN]);
+	assert!(copy[N_NO_NAN..].iter().all(|x| x.is_nan()));
+	// source unchanged
+	let bits = |s: &[f64]| s.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+	assert_eq!(bits(&array), bits(&TEST_ARRAY));
+
+	let rev = array.to_ord_subset_sorted_vec_rev();
+	assert_eq!(rev[0], INF);
+	assert!(rev[..N_NO_NAN].windows(2).all(|w| w[0] >= w[1]));
+
+	let pairs = [(3, 1.0), (1, NAN), (2, 1.0), (0, -1.0)];
+	let by_key = pairs.to_ord_subset_sorted_vec_by_key(|p| p.1);
+	let ids: Vec<_> = by_key.iter().map(|p| p.0).collect();
+	assert_eq!(ids, [0, 3, 2, 1]);
+	assert_eq!(pairs[0].0, 3);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn argsort() {
+	let keys = [2.0, NAN, 1.0, 2.0, NAN, 0.0];
+	let perm = keys.ord_subset_argsort();
+	// stable for equal and unordered values
+	assert_eq!(perm, [5, 2, 0, 3, 1, 4]);
+
+	let mut sorted = keys;
+	sorted.ord_subset_apply_permutation(&perm);
+	assert_eq!(&sorted[..4], [0.0, 1.0, 2.0, 2.0]);
+	assert!(sorted[4].is_nan() && sorted[5].is_nan());
+
+	let mut column = ['a', 'b', 'c', 'd', 'e', 'f'];
+	column.ord_subset_apply_permutation(&perm);
+	assert_eq!(column, ['f', 'c', 'a', 'd', 'b', 'e']);
+
+	let by_key = column.ord_subset_argsort_by_key(|&c| if c == 'a' { NAN } else { -(c as u8 as f64) });
+	assert_eq!(by_key, [0, 5, 3, 1, 4, 2]);
+
+	let empty: [f64; 0] = [];
+	assert!(empty.ord_subset_argsort().is_empty());
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_together() {
+	let mut xs = [NAN, 3.0, 1.0, NAN, 2.0, 1.0, NAN];
+	let mut labels = ["nan0", "c", "a0", "nan1", "b", "a1", "nan2"];
+	xs.ord_subset_sort_together(&mut labels);
+	assert_eq!(&xs[..4], [1.0, 1.0, 2.0, 3.0]);
+	assert!(xs[4..].iter().all(|x| x.is_nan()));
+	// stable for equal and unordered keys
+	assert_eq!(labels, ["a0", "a1", "b", "c", "nan0", "nan1", "nan2"]);
+}
+
+#[test]
+#[should_panic]
+#[cfg(feature="std")]
+fn sort_together_wrong_len() {
+	[1.0, 2.0].ord_subset_sort_together(&mut [1]);
+}
+
+#[test]
+#[should_panic]
+#[cfg(feature="std")]
+fn apply_permutation_invalid() {
+	[1, 2, 3].ord_subset_apply_permutation(&[0, 2, 2]);
+}
+
+// ----------------------------- unstable sorts --------------------------------
+
+#[test]
+fn sort_unstable() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable();
+	assert_eq!(&array[0..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN);
+}
+
+#[test]
+fn sort_unstable_rev() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev();
+
+	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
+	rev_sorted_array.reverse();
+
+	assert_eq!(&array[0..N_NO_NAN], &rev_sorted_array);
+}
+
+#[test]
+fn sort_unstable_rev_by() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev_by(|a, b| a.partial_cmp(b).unwrap());
+
+	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
+	rev_sorted_array.reverse();
+
+	assert_eq!(&array[0..N_NO_NAN], &rev_sorted_array);
+	assert!(array[N_NO_NAN..].iter().all(|num| num.is_nan()));
+}
+
+#[test]
+fn sort_unstable_by_key() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_by_key(key_function);
+	let mut std_sorted_array = TEST_ARRAY_NO_NAN;
+	std_sorted_array.sort_unstable_by_key(|num| OrdVar::new(key_function(num)));
+	assert_eq!(&array[..N_NO_NAN], &std_sorted_array);
+}
+
+#[test]
+fn sort_unstable_rev_by_key() {
+	// injective on TEST_ARRAY, so sort stability doesn't matter
+	fn key_function(el: &f64) -> f64 {
+		el * 2.0
+	}
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev_by_key(key_function);
+
+	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
+	rev_sorted_array.reverse();
+
+	assert_eq!(&array[..N_NO_NAN], &rev_sorted_array);
+	assert!(array[N_NO_NAN..].iter().all(|num| num.is_nan()));
+}
+
+#[test]
+fn sort_floats() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_floats();
+	assert_eq!(&array[..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN[..]);
+	assert!(array[N_NO_NAN..].iter().all(|x| x.is_nan()));
+
+	// same result as the generic sort, except for the order of equal zeros
+	let neg_nan = -NAN;
+	let special = [0.0, -0.0, INF, -INF, NAN, neg_nan, 1.0, -1.0, neg_nan, -0.0, f64::MIN_POSITIVE, f64::MAX, f64::MIN];
+	let mut floats = special;
+	let mut generic = special;
+	floats.ord_subset_sort_floats();
+	generic.ord_subset_sort_unstable();
+	let n_ordered = generic.ord_subset_ordered_prefix_len();
+	assert_eq!(n_ordered, special.len() - 3);
+	assert_eq!(floats.ord_subset_ordered_prefix_len(), n_ordered);
+	for (a, b) in floats[..n_ordered].iter().zip(generic[..n_ordered].iter()) {
+		assert!(a == b);
+		if *a != 0.0 {
+			assert_eq!(a.to_bits(), b.to_bits());
+		}
+	}
+	assert!(floats[n_ordered..].iter().all(|x| x.is_nan()));
+	// negative zeros first
+	assert_eq!(floats[3].to_bits(), (-0.0f64).to_bits());
+	assert_eq!(floats[4].to_bits(), (-0.0f64).to_bits());
+	assert_eq!(floats[5].to_bits(), 0.0f64.to_bits());
+
+	let mut floats = [2.0f32, -std::f32::NAN, 1.0, std::f32::NAN];
+	floats.ord_subset_sort_floats();
+	assert_eq!(&floats[..2], &[1.0, 2.0]);
+}
+
+#[test]
+fn sort_unstable_by_key_into() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	let mut array = TEST_ARRAY;
+	let mut keys = [0.0; N];
+	array.ord_subset_sort_unstable_by_key_into(&mut keys, key_function);
+	let mut std_sorted_array = TEST_ARRAY_NO_NAN;
+	std_sorted_array.sort_by_key(|num| OrdVar::new(key_function(num)));
+	assert_eq!(&array[..N_NO_NAN], &std_sorted_array);
+	assert!(array[N_NO_NAN..].iter().all(|num| num.is_nan()));
+	// keys are moved along
+	for (key, num) in keys.iter().zip(array.iter()).take(N_NO_NAN) {
+		assert_eq!(*key, key_function(num));
+	}
+
+	// the key function is called once per element
+	let mut calls = 0;
+	let mut small = [3, 1, 2, 0];
+	small.ord_subset_sort_unstable_by_key_into(&mut [0; 4], |&x| { calls += 1; x });
+	assert_eq!(small, [0, 1, 2, 3]);
+	assert_eq!(calls, 4);
+}
+
+#[test]
+#[should_panic]
+fn sort_unstable_by_key_into_wrong_len() {
+	[1.0, 2.0].ord_subset_sort_unstable_by_key_into(&mut [0.0], |&x| x);
+}
+
+// ----------------------------- counted sorts ---------------------------------
+
+#[test]
+fn sort_counted() {
+	fn check<F: Fn(&mut [f64]) -> usize>(sort: F) {
+		let mut array = TEST_ARRAY;
+		assert_eq!(sort(&mut array), N_NO_NAN);
+		assert!(array[..N_NO_NAN].iter().all(|num| !num.is_nan()));
+		assert!(array[N_NO_NAN..].iter().all(|num| num.is_nan()));
+
+		let mut array = TEST_ARRAY_NO_NAN;
+		assert_eq!(sort(&mut array), N_NO_NAN);
+
+		let mut array = [NAN; 4];
+		assert_eq!(sort(&mut array), 0);
+
+		assert_eq!(sort(&mut []), 0);
+	}
+
+	#[cfg(feature="std")]
+	{
+		check(|mut s| s.ord_subset_sort_counted());
+		check(|mut s| s.ord_subset_sort_rev_counted());
+		check(|mut s| s.ord_subset_sort_by_counted(|a, b| a.partial_cmp(b).unwrap()));
+		check(|mut s| s.ord_subset_sort_by_key_counted(|num| -num));
+	}
+	check(|mut s| s.ord_subset_sort_unstable_counted());
+	check(|mut s| s.ord_subset_sort_unstable_rev_counted());
+	check(|mut s| s.ord_subset_sort_unstable_by_counted(|a, b| a.partial_cmp(b).unwrap()));
+	check(|mut s| s.ord_subset_sort_unstable_by_key_counted(|num| -num));
+}
+
+#[test]
+fn sort_by_key_counted_unordered_keys() {
+	let mut array = [(1, 2.0), (2, NAN), (3, 1.0)];
+	assert_eq!(array.ord_subset_sort_unstable_by_key_counted(|&(_, k)| k), 2);
+	assert_eq!(array.ord_subset_sort_unstable_by_key_counted(|_| NAN), 0);
+}
+
+// ------------------------------- partitions ----------------------------------
+
+#[test]
+fn partition_unordered() {
+	let mut array = TEST_ARRAY;
+	assert_eq!(array.ord_subset_partition_unordered(), N_NO_NAN);
+	assert_eq!(&array[..N_NO_NAN], &TEST_ARRAY_NO_NAN);
+	assert!(array[N_NO_NAN..].iter().all(|num| num.is_nan()));
+
+	let mut array = TEST_ARRAY_NO_NAN;
+	assert_eq!(array.ord_subset_partition_unordered(), N_NO_NAN);
+	assert_eq!(array, TEST_ARRAY_NO_NAN);
+
+	assert_eq!([NAN; 5].ord_subset_partition_unordered(), 0);
+	assert_eq!(<[f64; 0]>::default().ord_subset_partition_unordered(), 0);
+}
+
+#[test]
+fn partition_unordered_by_key() {
+	// stable for both parts
+	let mut array = [(1, NAN), (2, 5.0), (3, NAN), (4, 1.0), (5, NAN), (6, 3.0), (7, 3.0)];
+	let mut n_calls = 0;
+	let n = array.ord_subset_partition_unordered_by_key(|&(_, k)| { n_calls += 1; k });
+	assert_eq!(n, 4);
+	assert_eq!(n_calls, array.len());
+	let order: Vec<_> = array.iter().map(|&(i, _)| i).collect();
+	assert_eq!(order, [2, 4, 6, 7, 1, 3, 5]);
+}
+
+#[test]
+fn swap_unordered_to_end() {
+	let mut array = TEST_ARRAY;
+	assert_eq!(array.ord_subset_swap_unordered_to_end(), N_NO_NAN);
+	assert_eq!(&array[..N_NO_NAN], &TEST_ARRAY_NO_NAN[..]);
+	assert!(array[N_NO_NAN..].iter().all(|x| x.is_nan()));
+
+	assert_eq!([NAN; 5].ord_subset_swap_unordered_to_end(), 0);
+	assert_eq!(<[f64; 0]>::default().ord_subset_swap_unordered_to_end(), 0);
+	let mut array = [NAN, 1.0, NAN, 2.0];
+	assert_eq!(array.ord_subset_swap_unordered_to_end(), 2);
+	assert_eq!(&array[..2], &[1.0, 2.0]);
+}
+
+#[test]
+fn is_partitioned() {
+	assert!(!TEST_ARRAY.ord_subset_is_partitioned());
+	assert!(SORTED_TEST_ARRAY.ord_subset_is_partitioned());
+	assert!(![1.0, NAN, 2.0].ord_subset_is_partitioned());
+	assert!([3.0, 1.0, 2.0, NAN].ord_subset_is_partitioned());
+	assert!([NAN, NAN].ord_subset_is_partitioned());
+	assert!(<[f64; 0]>::default().ord_subset_is_partitioned());
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_partition_unordered();
+	assert!(array.ord_subset_is_partitioned());
+}
+
+#[test]
+fn is_partitioned_by_key() {
+	let pairs = [(1, 2.0), (0, 1.0), (2, NAN), (3, NAN)];
+	assert!(pairs.ord_subset_is_partitioned_by_key(|p| p.1));
+	assert!(!pairs.ord_subset_is_partitioned_by_key(|p| if p.0 == 1 { NAN } else { p.1 }));
+	assert!(pairs.ord_subset_is_partitioned_by_key(|_| NAN));
+	assert!(<[(u8, f64); 0]>::default().ord_subset_is_partitioned_by_key(|p| p.1));
+}
+
+// ------------------------- unordered value policy ----------------------------
+
+#[test]
+#[cfg(feature="std")]
+fn sort_with_policy() {
+	let n_nan = N - N_NO_NAN;
+	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
+	rev_sorted_array.reverse();
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_with(UnorderedPolicy::Back);
+	assert_eq!(&array[..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN);
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_with(UnorderedPolicy::Front);
+	assert!(array[..n_nan].iter().all(|num| num.is_nan()));
+	assert_eq!(&array[n_nan..], &SORTED_TEST_ARRAY_NO_NAN);
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_rev_with(UnorderedPolicy::Back);
+	assert_eq!(&array[..N_NO_NAN], &rev_sorted_array);
+
+	// the unordered values stay in front, only the ordered ones are reversed
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_rev_with(UnorderedPolicy::Front);
+	assert!(array[..n_nan].iter().all(|num| num.is_nan()));
+	assert_eq!(&array[n_nan..], &rev_sorted_array);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn sort_by_key_with_policy() {
+	// stable, unordered keys keep their original order
+	let mut array = [(1, 2.0), (2, NAN), (3, 1.0), (4, NAN), (5, 2.0)];
+	array.ord_subset_sort_by_key_with(|&(_, k)| k, UnorderedPolicy::Front);
+	let order: Vec<_> = array.iter().map(|&(i, _)| i).collect();
+	assert_eq!(order, [2, 4, 3, 1, 5]);
+
+	array.ord_subset_sort_by_key_with(|&(_, k)| k, UnorderedPolicy::Back);
+	let order: Vec<_> = array.iter().map(|&(i, _)| i).collect();
+	assert_eq!(order, [3, 1, 5, 2, 4]);
+}
+
+#[test]
+fn sort_unstable_with_policy() {
+	let n_nan = N - N_NO_NAN;
+	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
+	rev_sorted_array.reverse();
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_with(UnorderedPolicy::Front);
+	assert!(array[..n_nan].iter().all(|num| num.is_nan()));
+	assert_eq!(&array[n_nan..], &SORTED_TEST_ARRAY_NO_NAN);
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev_with(UnorderedPolicy::Front);
+	assert!(array[..n_nan].iter().all(|num| num.is_nan()));
+	assert_eq!(&array[n_nan..], &rev_sorted_array);
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev_with(UnorderedPolicy::Back);
+	assert_eq!(&array[..N_NO_NAN], &rev_sorted_array);
+
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_by_key_with(|num| -num, UnorderedPolicy::Front);
+	assert!(array[..n_nan].iter().all(|num| num.is_nan()));
+	assert_eq!(&array[n_nan..], &rev_sorted_array);
+}
+
+// ---------------------------- binary searches --------------------------------
+
+#[test]
+fn binary_search() {
+	let array = SORTED_TEST_ARRAY;
+	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
+		assert_eq!(array.ord_subset_binary_search(num), Ok(i));
+	}
+}
+
+#[test]
+fn binary_search_in_prefix() {
+	let array = SORTED_TEST_ARRAY;
+	let n_ordered = array.ord_subset_ordered_prefix_len();
+	assert_eq!(n_ordered, N_NO_NAN);
+	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
+		assert_eq!(array.ord_subset_binary_search_in_prefix(n_ordered, |x| x.partial_cmp(num).unwrap()), Ok(i));
+	}
+	// never called on values outside the order
+	let less = |x: &f64| {
+		assert!(!x.is_nan());
+		std::cmp::Ordering::Less
+	};
+	assert_eq!(array.ord_subset_binary_search_in_prefix(n_ordered, less), Err(N_NO_NAN));
+	// searching a shorter prefix
+	assert_eq!(array.ord_subset_binary_search_in_prefix(3, less), Err(3));
+}
+
+#[test]
+fn binary_search_rev() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev();
+	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
+		assert_eq!(array.ord_subset_binary_search_rev(num), Ok(i));
+	}
+}
+
+#[test]
+fn binary_search_by_key() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_by_key(key_function);
+	for num in array.iter().take(N_NO_NAN) {
+		let key = key_function(num);
+		match array.ord_subset_binary_search_by_key(&key, key_function) {
+			Err(_) => panic!("Did not find correct location of element"),
+			Ok(pos) => assert_eq!(key_function(&array[pos]), key),
+		}
+	}
+}
+
+#[test]
+fn binary_search_by_key_borrowed() {
+	struct Entry {
+		name: String,
+		score: f64,
+	}
+	let entry = |name: &str, score| Entry { name: name.to_string(), score };
+	let mut entries = vec![entry("b", 1.0), entry("d", NAN), entry("a", 3.0), entry("c", 2.0)];
+
+	// keys borrowed from the elements, searched by `&str` without allocating
+	entries.ord_subset_sort_unstable_by_key_ref(|e| e.name.as_str());
+	assert_eq!(entries.ord_subset_binary_search_by_key_borrowed("c", |e| e.name.as_str()), Ok(2));
+	assert_eq!(entries.ord_subset_binary_search_by_key_borrowed("bb", |e| e.name.as_str()), Err(2));
+	// `String: Borrow<str>`
+	assert_eq!(entries.ord_subset_binary_search_by_key_borrowed("a", |e| &e.name), Ok(0));
+
+	// float keys, NaN at the end
+	entries.ord_subset_sort_unstable_by_key(|e| e.score);
+	assert_eq!(entries.ord_subset_binary_search_by_key_borrowed(&2.0, |e| &e.score), Ok(1));
+	assert_eq!(entries.ord_subset_binary_search_by_key_borrowed(&2.5, |e| &e.score), Err(2));
+	assert_eq!(entries.ord_subset_binary_search_by_key_borrowed(&4.0, |e| &e.score), Err(3));
+}
+
+#[test]
+fn try_binary_search() {
+	let array = SORTED_TEST_ARRAY;
+	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
+		assert_eq!(array.try_ord_subset_binary_search(num), Ok(Ok(i)));
+		assert_eq!(array.try_ord_subset_binary_search_by_key(num, |&x| x), Ok(Ok(i)));
+	}
+	assert_eq!(array.try_ord_subset_binary_search(&NAN), Err(SearchError::NeedleUnordered));
+	assert_eq!(array.try_ord_subset_binary_search_rev(&NAN), Err(SearchError::NeedleUnordered));
+	assert_eq!(array.try_ord_subset_binary_search_by_key(&NAN, |&x| x), Err(SearchError::NeedleUnordered));
+	assert_eq!(array.try_ord_subset_binary_search(&100.0), Ok(Err(N_NO_NAN - 1)));
+
+	let mut rev = array;
+	rev.ord_subset_sort_unstable_rev();
+	assert_eq!(rev.try_ord_subset_binary_search_rev(&rev[3]), Ok(Ok(3)));
+
+	// not partitioned, the search runs into the NaN
+	let unsorted = [1.0, NAN, 2.0, 3.0];
+	assert_eq!(unsorted.try_ord_subset_binary_search(&1.5), Err(SearchError::ContractViolation));
+	assert_eq!(SearchError::NeedleUnordered.to_string(), "Attempted binary search for value outside total order");
+}
+
+#[test]
+fn binary_search_range() {
+	let array = SORTED_TEST_ARRAY;
+	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
+		assert_eq!(array.ord_subset_binary_search_range(num), i..i+1);
+		if num.is_finite() {
+			assert_eq!(array.ord_subset_binary_search_range(&(num + 0.5)), i+1..i+1);
+		}
+	}
+
+	let array = [1.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 3.0, NAN, NAN];
+	assert_eq!(array.ord_subset_binary_search_range(&2.0), 1..7);
+	assert_eq!(array.ord_subset_binary_search_range(&0.0), 0..0);
+	assert_eq!(array.ord_subset_binary_search_range(&INF), 8..8);
+
+	let array = [2.0; 5];
+	assert_eq!(array.ord_subset_binary_search_range(&2.0), 0..5);
+	assert_eq!(<[f64; 0]>::default().ord_subset_binary_search_range(&2.0), 0..0);
+}
+
+#[test]
+#[should_panic]
+fn binary_search_range_unordered() {
+	SORTED_TEST_ARRAY.ord_subset_binary_search_range(&NAN);
+}
+
+#[test]
+fn closest() {
+	let array = SORTED_TEST_ARRAY;
+	assert_eq!(array.ord_subset_closest(&3.4), Some((4, &3.0)));
+	assert_eq!(array.ord_subset_closest(&3.6), Some((5, &4.0)));
+	// ties go to the lower index
+	assert_eq!(array.ord_subset_closest(&3.5), Some((4, &3.0)));
+	assert_eq!(array.ord_subset_closest(&7.0), Some((8, &7.0)));
+	assert_eq!(array.ord_subset_closest(&1e300), Some((28, &27.0)));
+	assert_eq!(array.ord_subset_closest(&-INF), Some((0, &-INF)));
+	assert_eq!(array.ord_subset_closest(&-1e300), Some((1, &0.0)));
+	assert_eq!(array.ord_subset_closest(&NAN), None);
+
+	let finite = [1.0f32, 2.0, NAN as f32];
+	assert_eq!(finite.ord_subset_closest(&std::f32::INFINITY), Some((1, &2.0)));
+	assert_eq!(finite.ord_subset_closest(&std::f32::NEG_INFINITY), Some((0, &1.0)));
+	assert_eq!([NAN].ord_subset_closest(&1.0), None);
+	assert_eq!(<[f64; 0]>::default().ord_subset_closest(&1.0), None);
+
+	let ints = [10u32, 20, 40];
+	let dist = |a: &u32, b: &u32| a.abs_diff(*b);
+	assert_eq!(ints.ord_subset_closest_by(&29, dist), Some((1, &20)));
+	assert_eq!(ints.ord_subset_closest_by(&30, dist), Some((1, &20)));
+	assert_eq!(ints.ord_subset_closest_by(&31, dist), Some((2, &40)));
+	assert_eq!(ints.ord_subset_closest_by(&0, dist), Some((0, &10)));
+}
+
+#[test]
+fn binary_search_first_last() {
+	let array = [1.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 3.0, 3.0, NAN, NAN];
+	assert_eq!(array.ord_subset_binary_search_first(&2.0), Ok(1));
+	assert_eq!(array.ord_subset_binary_search_last(&2.0), Ok(6));
+	assert_eq!(array.ord_subset_binary_search_first(&3.0), Ok(7));
+	assert_eq!(array.ord_subset_binary_search_last(&3.0), Ok(8));
+	assert_eq!(array.ord_subset_binary_search_first(&0.0), Err(0));
+	assert_eq!(array.ord_subset_binary_search_last(&2.5), Err(7));
+	assert_eq!(array.ord_subset_binary_search_last(&INF), Err(9));
+
+	let array = [2.0; 50];
+	assert_eq!(array.ord_subset_binary_search_first(&2.0), Ok(0));
+	assert_eq!(array.ord_subset_binary_search_last(&2.0), Ok(49));
+	assert_eq!(<[f64; 0]>::default().ord_subset_binary_search_last(&2.0), Err(0));
+
+	// agrees with the range
+	let array = SORTED_TEST_ARRAY;
+	for num in array.iter().take(N_NO_NAN) {
+		let range = array.ord_subset_binary_search_range(num);
+		assert_eq!(array.ord_subset_binary_search_first(num), Ok(range.start));
+		assert_eq!(array.ord_subset_binary_search_last(num), Ok(range.end - 1));
+	}
+}
+
+#[test]
+fn binary_search_first_last_by_key() {
+	let array = [(0, 1.0), (1, 2.0), (2, 2.0), (3, 2.0), (4, NAN)];
+	assert_eq!(array.ord_subset_binary_search_first_by_key(&2.0, |&(_, k)| k), Ok(1));
+	assert_eq!(array.ord_subset_binary_search_last_by_key(&2.0, |&(_, k)| k), Ok(3));
+	assert_eq!(array.ord_subset_binary_search_first_by_key(&5.0, |&(_, k)| k), Err(4));
+}
+
+#[test]
+#[should_panic]
+fn binary_search_first_unordered() {
+	let _ = SORTED_TEST_ARRAY.ord_subset_binary_search_first(&NAN);
+}
+
+#[test]
+fn contains() {
+	let array = SORTED_TEST_ARRAY;
+	for num in array.iter().take(N_NO_NAN) {
+		assert!(array.ord_subset_contains(num));
+		assert!(!array.ord_subset_contains(&(num + 0.5)) || num.is_infinite());
+	}
+	assert!(!array.ord_subset_contains(&NAN));
+
+	let array = [(3, 1.0), (1, 2.0), (2, NAN)];
+	assert!(array.ord_subset_contains_by_key(&2.0, |&(_, k)| k));
+	assert!(!array.ord_subset_contains_by_key(&3.0, |&(_, k)| k));
+	assert!(!array.ord_subset_contains_by_key(&NAN, |&(_, k)| k));
+}
+
+#[test]
+fn ordered_prefix_len() {
+	assert_eq!(SORTED_TEST_ARRAY.ord_subset_ordered_prefix_len(), N_NO_NAN);
+	assert_eq!(SORTED_TEST_ARRAY_NO_NAN.ord_subset_ordered_prefix_len(), N_NO_NAN);
+	assert_eq!([NAN; 3].ord_subset_ordered_prefix_len(), 0);
+	assert_eq!(<[f64; 0]>::default().ord_subset_ordered_prefix_len(), 0);
+
+	let array = [(1, 1.0), (2, 2.0), (0, NAN)];
+	assert_eq!(array.ord_subset_ordered_prefix_len_by_key(|&(_, k)| k), 2);
+	assert_eq!(array.ord_subset_ordered_prefix_len_by_key(|&(i, _)| i), 3);
+	assert_eq!(array.ord_subset_ordered_prefix_len_by_key(|_| NAN), 0);
+}
+
+// ------ binary search error cases ------
+
+#[test]
+fn binary_search_err() {
+	let array = SORTED_TEST_ARRAY;
+	for (i, num) in array.iter()
+		.enumerate()
+		.filter(|&(_, num)| num.is_finite())
+	{
+		let new_num = num + 0.5;
+		assert_eq!(array.ord_subset_binary_search(&new_num), Err(i+1));
+	}
+}
+
+#[test]
+fn binary_search_rev_err() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_rev();
+	for (i, num) in array.iter()
+		.enumerate()
+		.filter(|&(_, num)| num.is_finite())
+	{
+		let new_num = num + 0.5;
+		assert_eq!(array.ord_subset_binary_search_rev(&new_num), Err(i));
+	}
+}
+
+#[test]
+fn binary_search_by_key_err() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	let mut array = TEST_ARRAY;
+	array.ord_subset_sort_unstable_by_key(key_function);
+	for num in array.iter().take(N_NO_NAN) {
+		let key_diff = key_function(&(num+0.01))*1.01 + 0.01;
+		let pos = array.ord_subset_binary_search_by_key(&key_diff, key_function);
+		let pos_std = (&array[..N_NO_NAN]).binary_search_by_key(
+			&OrdVar::new(key_diff),
+			|num| OrdVar::new(key_function(num))
+		);
+		match (pos, pos_std) {
+			(Err(pos), Err(pos_std)) => assert!(pos == pos_std),
+			// the commented out match branch is also valid behaviour
+			// but this function is supposed to test as many error cases as possible
+			// by choosing key_diff the right way
+			//(Ok(pos), Ok(pos_std)) => {
+			//	let key1 = key_function(&array[pos]);
+			//	let key2 = key_function(&array[pos_std]);
+			//	assert!(key1 == key2);
+			//},
+			_ => panic!("Inconsistency between this library's and std's binary_search_by_key"),
+		}
+	}
+}
+
+// ------------------------------ comparators ----------------------------------
+#[test]
+fn cmp_functions() {
+	use std::cmp::Ordering::*;
+	use ord_subset::{ord_subset_cmp, ord_subset_partial_cmp};
+	assert_eq!(ord_subset_cmp(&1.0, &2.0), Less);
+	assert_eq!(ord_subset_cmp(&NAN, &INF), Greater);
+	assert_eq!(ord_subset_cmp(&INF, &NAN), Less);
+	assert_eq!(ord_subset_cmp(&NAN, &NAN), Equal);
+	assert_eq!(ord_subset_partial_cmp(&NAN, &1.0), Some(Greater));
+	assert_eq!(ord_subset_partial_cmp(&NAN, &NAN), Some(Equal));
+	assert_eq!(ord_subset_partial_cmp(&2.0, &1.0), Some(Greater));
+
+	let mut array = TEST_ARRAY;
+	array.sort_by(ord_subset_cmp);
+	assert_eq!(&array[..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN[..]);
+}
+
+// -------------------------------- extrema ------------------------------------
+
+#[test]
+fn slice_max_min() {
+	assert_eq!(TEST_ARRAY.ord_subset_max(), Some(&INF));
+	assert_eq!(TEST_ARRAY.ord_subset_min(), Some(&-INF));
+
+	let empty: [f64; 0] = [];
+	assert_eq!(empty.ord_subset_max(), None);
+	assert_eq!([NAN, NAN].ord_subset_min(), None);
+}
+
+#[test]
+fn slice_max_min_by_key() {
+	fn key_function(el: &f64) -> f64 {
+		(el - 13.0).recip()
+	}
+	// key is NAN for NAN, 0 for ±INF and ±INF for 13.0
+	assert_eq!(TEST_ARRAY.ord_subset_max_by_key(key_function), Some(&13.0));
+	assert_eq!(TEST_ARRAY.ord_subset_min_by_key(|num| -key_function(num)), Some(&13.0));
+	assert_eq!([NAN, 1.0].ord_subset_max_by_key(|_| NAN), None);
+}
+
+#[test]
+fn slice_max_min_tie_breaking() {
+	// ties are decided by the key only
+	let arr = [(1, 1.0), (2, NAN), (3, 3.0), (4, 3.0), (5, 1.0)];
+	assert_eq!(arr.ord_subset_max_by_key(|&(_, k)| k), Some(&(4, 3.0)));
+	assert_eq!(arr.ord_subset_min_by_key(|&(_, k)| k), Some(&(1, 1.0)));
+
+	let by_key = |a: &(i32, f64), b: &(i32, f64)| a.1.partial_cmp(&b.1).unwrap();
+	let arr = [(1, 1.0), (3, 3.0), (4, 3.0), (5, 1.0)];
+	assert_eq!(arr.ord_subset_max_by(by_key), Some(&(4, 3.0)));
+	assert_eq!(arr.ord_subset_min_by(by_key), Some(&(1, 1.0)));
+}
+
+#[test]
+fn slice_max_min_index() {
+	assert_eq!(TEST_ARRAY.ord_subset_max_index(), Some(3));
+	assert_eq!(TEST_ARRAY.ord_subset_min_index(), Some(18));
+
+	// indices count unordered values, ties resolved like the iterator methods
+	let arr = [NAN, 1.0, 3.0, NAN, 3.0, 1.0];
+	assert_eq!(arr.ord_subset_max_index(), Some(4));
+	assert_eq!(arr.ord_subset_min_index(), Some(1));
+
+	assert_eq!([NAN, NAN].ord_subset_max_index(), None);
+	assert_eq!(<[f64; 0]>::default().ord_subset_min_index(), None);
+}
+
+#[test]
+fn slice_max_min_with_index() {
+	assert_eq!(TEST_ARRAY.ord_subset_max_with_index(), Some((3, &INF)));
+	assert_eq!(TEST_ARRAY.ord_subset_min_with_index(), Some((18, &TEST_ARRAY[18])));
+
+	let arr = [NAN, 1.0, 3.0, NAN, 3.0, 1.0];
+	assert_eq!(arr.ord_subset_max_with_index(), Some((4, &3.0)));
+	assert_eq!(arr.ord_subset_min_with_index(), Some((1, &1.0)));
+	assert_eq!([NAN, NAN].ord_subset_min_with_index(), None);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn slice_max_min_set() {
+	let arr = [NAN, 1.0, 3.0, NAN, 3.0, 1.0, 2.0];
+	let max = arr.ord_subset_max_set();
+	assert_eq!(max, [&3.0, &3.0]);
+	assert!(std::ptr::eq(max[0], &arr[2]) && std::ptr::eq(max[1], &arr[4]));
+	let min = arr.ord_subset_min_set();
+	assert!(std::ptr::eq(min[0], &arr[1]) && std::ptr::eq(min[1], &arr[5]));
+	assert_eq!(min.len(), 2);
+	assert_eq!([7.0].ord_subset_max_set(), [&7.0]);
+	assert!([NAN, NAN].ord_subset_max_set().is_empty());
+	assert!(<[f64; 0]>::default().ord_subset_min_set().is_empty());
+}
+
+#[test]
+fn slice_max_min_index_by_key() {
+	let arr = [(1, 1.0), (2, NAN), (3, 3.0), (4, 3.0), (5, 1.0)];
+	assert_eq!(arr.ord_subset_max_index_by_key(|&(_, k)| k), Some(3));
+	assert_eq!(arr.ord_subset_min_index_by_key(|&(_, k)| k), Some(0));
+	assert_eq!(arr.ord_subset_max_index_by_key(|_| NAN), None);
+}
+
+#[test]
+fn slice_extrema_by_key() {
+	let arr = [(1, 1.0), (2, NAN), (3, 3.0), (4, 3.0), (5, 1.0)];
+	let mut n_calls = 0;
+	assert_eq!(arr.ord_subset_extrema_by_key(|&(_, k)| { n_calls += 1; k }), Some((0, 3)));
+	assert_eq!(n_calls, arr.len());
+	assert_eq!(arr.ord_subset_extrema_by_key(|&(_, k)| -k), Some((2, 4)));
+	assert_eq!(arr.ord_subset_extrema_by_key(|_| NAN), None);
+
+	// same as separate searches
+	let extrema = TEST_ARRAY.ord_subset_extrema_by_key(|&num| num);
+	assert_eq!(extrema, Some((18, 3)));
+	for &(a, b) in &[(NAN, 1.0), (1.0, NAN), (1.0, 1.0), (2.0, 1.0), (1.0, 2.0)] {
+		let arr = [a, b];
+		let expected = arr.ord_subset_min_index().and_then(|min| arr.ord_subset_max_index().map(|max| (min, max)));
+		assert_eq!(arr.ord_subset_extrema_by_key(|&num| num), expected);
+	}
+}
+
+#[test]
+fn slice_minmax() {
+	assert_eq!(TEST_ARRAY.ord_subset_minmax(), Some((&TEST_ARRAY[18], &INF)));
+	assert_eq!(TEST_ARRAY.ord_subset_minmax_indices(), Some((18, 3)));
+
+	let arr = [NAN, 1.0, 3.0, NAN, 3.0, 1.0];
+	assert_eq!(arr.ord_subset_minmax_indices(), Some((1, 4)));
+	let (min, max) = arr.ord_subset_minmax().unwrap();
+	assert!(std::ptr::eq(min, &arr[1]) && std::ptr::eq(max, &arr[4]));
+
+	// single element is both
+	assert_eq!([NAN, 5.0].ord_subset_minmax(), Some((&5.0, &5.0)));
+	assert_eq!([NAN, 5.0].ord_subset_minmax_indices(), Some((1, 1)));
+	// nothing ordered
+	assert_eq!([NAN, NAN].ord_subset_minmax(), None);
+	assert_eq!([NAN, NAN].ord_subset_minmax_indices(), None);
+	assert_eq!(<[f64; 0]>::default().ord_subset_minmax(), None);
+
+	let arr = [(1, 1.0), (2, NAN), (3, 3.0), (4, 3.0), (5, 1.0)];
+	assert_eq!(arr.ord_subset_minmax_by_key(|&(_, k)| -k), Some((&arr[2], &arr[4])));
+	assert_eq!(arr.ord_subset_minmax_by_key(|_| NAN), None);
+}
+
+#[test]
+#[cfg(feature="std")]
+fn slice_rank() {
+	let ranks = TEST_ARRAY.ord_subset_rank();
+	for (num, rank) in TEST_ARRAY.iter().zip(ranks) {
+		let expected = SORTED_TEST_ARRAY_NO_NAN.iter().position(|n| n == num);
+		assert_eq!(rank, expected);
+	}
+
+	let arr = [2.0, 1.0, NAN, 2.0, 1.0, 0.0];
+	assert_eq!(arr.ord_subset_rank(), vec![Some(3), Some(1), None, Some(3), Some(1), Some(0)]);
+	assert_eq!([NAN].ord_subset_rank(), vec![None]);
+}
+
+// -------------------------------- chunks -------------------------------------
+
+#[test]
+fn chunk_by() {
+	let array = SORTED_TEST_ARRAY;
+	let mut chunks = array.ord_subset_chunk_by();
+	for num in &SORTED_TEST_ARRAY_NO_NAN {
+		assert_eq!(chunks.next(), Some(&[*num][..]));
+	}
+	assert_eq!(chunks.next().map(|chunk| chunk.len()), Some(N - N_NO_NAN));
+	assert_eq!(chunks.next(), None);
+
+	let arr = [1.0, 1.0, NAN, 1.0, 2.0, 2.0];
+	let chunks: Vec<_> = arr.ord_subset_chunk_by().map(|chunk| chunk.len()).collect();
+	assert_eq!(chunks, [2, 1, 1, 2]);
+
+	let empty: [f64; 0] = [];
+	assert_eq!(empty.ord_subset_chunk_by().next(), None);
+}
+
+// ---------------------------- sliding windows --------------------------------
+#[cfg(feature="std")]
+#[test]
+fn sliding_max_min() {
+	for w in 1..N + 2 {
+		let max = TEST_ARRAY.ord_subset_sliding_max(w);
+		let min = TEST_ARRAY.ord_subset_sliding_min(w);
+		let windows: Vec<_> = TEST_ARRAY.windows(w).collect();
+		assert_eq!(max.len(), windows.len());
+		assert_eq!(min.len(), windows.len());
+		for (i, window) in windows.iter().enumerate() {
+			assert_eq!(max[i], window.ord_subset_max());
+			assert_eq!(min[i], window.ord_subset_min());
+		}
+	}
+	assert_eq!([NAN, NAN, 1.0].ord_subset_sliding_min(2), [None, Some(&1.0)]);
+}
+
+#[cfg(feature="std")]
+#[test]
+fn sliding_max_min_ties() {
+	let arr = [1.0, 1.0, 0.0];
+	let max = arr.ord_subset_sliding_max(2);
+	assert!(std::ptr::eq(max[0].unwrap(), &arr[1]));
+	let min = arr.ord_subset_sliding_min(3);
+	assert!(std::ptr::eq(min[0].unwrap(), &arr[2]));
+	let min = arr.ord_subset_sliding_min(2);
+	assert!(std::ptr::eq(min[0].unwrap(), &arr[0]));
+}
+
+#[cfg(feature="std")]
+#[test]
+#[should_panic]
+fn sliding_max_zero_width() {
+	let _ = TEST_ARRAY.ord_subset_sliding_max(0);
+}
+
+// -------------------------------- merges -------------------------------------
+#[cfg(feature="std")]
+#[test]
+fn merge() {
+	// overlapping
+	let merged = [1.0, 3.0, 5.0].ord_subset_merge(&[2.0, 3.0, 4.0, 6.0]);
+	assert_eq!(merged, [1.0, 2.0, 3.0, 3.0, 4.0, 5.0, 6.0]);
+	// disjoint
+	assert_eq!([4.0, 5.0].ord_subset_merge(&[1.0, 2.0]), [1.0, 2.0, 4.0, 5.0]);
+	assert_eq!([1.0, 2.0].ord_subset_merge(&[]), [1.0, 2.0]);
+
+	// both ending in unordered runs
+	let (left, right) = SORTED_TEST_ARRAY.split_at(N / 2);
+	let mut left = left.to_vec();
+	left.push(NAN);
+	let merged = left.ord_subset_merge(right);
+	assert_eq!(&merged[..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN[..]);
+	assert_eq!(merged.len(), N + 1);
+	assert!(merged[N_NO_NAN..].iter().all(|x| x.is_nan()));
+}
+
+#[cfg(feature="std")]
+#[test]
+fn merge_by_key() {
+	let left = [(0, 1.0), (1, 2.0), (2, NAN)];
+	let right = [(3, 1.0), (4, 3.0), (5, NAN)];
+	let merged = left.ord_subset_merge_by_key(&right, |&(_, k)| k);
+	let ids: Vec<_> = merged.iter().map(|&(id, _)| id).collect();
+	// stable, unordered tail of left first
+	assert_eq!(ids, [0, 3, 1, 4, 2, 5]);
+}
+
+#[cfg(feature="std")]
+#[test]
+fn merge_into() {
+	let mut out = vec![0.0];
+	[1.0, NAN].ord_subset_merge_into(&[2.0], &mut out);
+	assert_eq!(&out[..3], &[0.0, 1.0, 2.0]);
+	assert!(out[3].is_nan());
+}
+
+// ------------------------------- quantiles -----------------------------------
+
+#[test]
+fn quantile() {
+	let array = SORTED_TEST_ARRAY;
+	assert_eq!(array.ord_subset_quantile(0.0), Some(&-INF));
+	assert_eq!(array.ord_subset_quantile(1.0), Some(&INF));
+	// 30 ordered values, rank = ceil(0.5 * 30) = 15
+	assert_eq!(array.ord_subset_quantile(0.5), Some(&13.0));
+	// rank = ceil(0.95 * 30) = 29
+	assert_eq!(array.ord_subset_quantile(0.95), Some(&27.0));
+	assert_eq!(array.ord_subset_percentile(95.0), Some(&27.0));
+
+	assert_eq!(array.ord_subset_quantile(-0.1), None);
+	assert_eq!(array.ord_subset_quantile(NAN), None);
+	assert_eq!(array.ord_subset_percentile(101.0), None);
+	assert_eq!([NAN, NAN].ord_subset_quantile(0.5), None);
+}
+
+#[test]
+fn quantile_rank_rounding() {
+	// 0.55 * 100.0 and 0.07 * 100.0 are slightly above the integer, 55.0 / 100.0 * 100.0 too
+	let array: Vec<f64> = (1..=100).map(f64::from).collect();
+	assert_eq!(array.ord_subset_percentile(55.0), Some(&55.0));
+	assert_eq!(array.ord_subset_percentile(7.0), Some(&7.0));
+	assert_eq!(array.ord_subset_quantile(0.55), Some(&55.0));
+	assert_eq!(array.ord_subset_quantile(0.07), Some(&7.0));
+	for p in 1..=100 {
+		assert_eq!(array.ord_subset_percentile(f64::from(p)), Some(&f64::from(p)));
+		assert_eq!(array.ord_subset_quantile(f64::from(p) / 100.0), Some(&f64::from(p)));
+	}
+	// still rounds genuine fractions up
+	assert_eq!(array.ord_subset_percentile(55.5), Some(&56.0));
+	assert_eq!(array.ord_subset_quantile(0.551), Some(&56.0));
+}
+
+#[test]
+#[cfg(feature="std")]
+fn quantiles() {
+	let qs = [0.0, 0.25, 0.5, 0.9, 0.95, 1.0];
+	let mut array = TEST_ARRAY;
+	let quantiles = array.ord_subset_quantiles(&qs);
+	// same as the single quantile on the sorted slice
+	let expected: Vec<_> = qs.iter().map(|&q| SORTED_TEST_ARRAY.ord_subset_quantile(q).cloned()).collect();
+	assert_eq!(quantiles, expected);
+	assert_eq!(&quantiles[..3], &[Some(-INF), Some(6.0), Some(13.0)]);
+	assert_eq!([NAN, NAN].ord_subset_quantiles(&[0.5]), [None]);
+	assert!(array.ord_subset_quantiles(&[]).is_empty());
+
+	// rank rounding like `ord_subset_quantile`
+	let mut array: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+	assert_eq!(array.ord_subset_quantiles(&[0.07, 0.55]), [Some(7.0), Some(55.0)]);
+}
+
+#[test]
+#[should_panic(expected = "Quantile is not within [0, 1]: 1.1")]
+#[cfg(feature="std")]
+fn quantiles_out_of_range() {
+	let mut array = TEST_ARRAY;
+	array.ord_subset_quantiles(&[0.5, 1.1]);
+}
+
+#[test]
+#[should_panic(expected = "Quantile is not within [0, 1]: NaN")]
+#[cfg(feature="std")]
+fn quantiles_nan() {
+	[1.0].ord_subset_quantiles(&[NAN]);
+}
+
+#[test]
+fn percentile_sorted() {
+	let s = [1.0, 2.0, 4.0, 8.0, NAN, NAN];
+	// h = 3 * p / 100
+	assert_eq!(s.ord_subset_percentile_sorted(0.0), Some(1.0));
+	assert_eq!(s.ord_subset_percentile_sorted(25.0), Some(1.75));
+	assert_eq!(s.ord_subset_percentile_sorted(50.0), Some(3.0));
+	assert_eq!(s.ord_subset_percentile_sorted(75.0), Some(5.0));
+	assert_eq!(s.ord_subset_percentile_sorted(100.0), Some(8.0));
+	assert_eq!(s.ord_subset_percentile_sorted(101.0), None);
+	assert_eq!(s.ord_subset_percentile_sorted(NAN), None);
+	assert_eq!([NAN].ord_subset_percentile_sorted(50.0), None);
+	assert_eq!([5.0f32].ord_subset_percentile_sorted(30.0), Some(5.0));
+
+	let infs = [-INF, -INF, 0.0, INF, INF];
+	assert_eq!(infs.ord_subset_percentile_sorted(10.0), Some(-INF));
+	assert_eq!(infs.ord_subset_percentile_sorted(25.0), Some(-INF));
+	assert_eq!(infs.ord_subset_percentile_sorted(60.0), Some(INF));
+	assert_eq!(infs.ord_subset_percentile_sorted(100.0), Some(INF));
+	// one infinite neighbour
+	assert_eq!([-INF, 0.0].ord_subset_percentile_sorted(50.0), Some(-INF));
+	assert_eq!([0.0, INF].ord_subset_percentile_sorted(50.0), Some(INF));
+	assert_eq!([0.0, INF, NAN].ord_subset_percentile_sorted(1.0), Some(INF));
+	assert_eq!([-INF, INF].ord_subset_percentile_sorted(50.0), Some(-INF));
+}
+
+#[test]
+fn median() {
+	let mut array = TEST_ARRAY;
+	// lower median, same as the nearest-rank quantile
+	assert_eq!(array.ord_subset_median(), Some(&13.0));
+	assert!(array[N_NO_NAN..].iter().all(|x| x.is_nan()));
+	let mut array = TEST_ARRAY;
+	assert_eq!(array.ord_subset_median_interpolated(), Some(13.5));
+
+	// 5 numbers and 3 NaNs
+	let mut array = [NAN, 9.0, 1.0, NAN, 7.0, 3.0, NAN, 5.0];
+	assert_eq!(array.ord_subset_median(), Some(&5.0));
+	assert_eq!(array.ord_subset_median_interpolated(), Some(5.0));
+
+	let mut array = [std::f32::MAX, std::f32::MAX];
+	assert_eq!(array.ord_subset_median_interpolated(), Some(std::f32::MAX as f64));
+	assert_eq!([1.0, INF].ord_subset_median_interpolated(), Some(INF));
+	assert_eq!([-INF, -INF].ord_subset_median_interpolated(), Some(-INF));
+	assert_eq!([-INF, NAN, INF].ord_subset_median_interpolated(), None);
+	assert_eq!([7.0].ord_subset_median(), Some(&7.0));
+	assert_eq!([NAN, NAN].ord_subset_median(), None);
+	assert_eq!([NAN].ord_subset_median_interpolated(), None);
+	assert_eq!(<[f64; 0]>::default().ord_subset_median_interpolated(), None);
+}
+
+#[test]
+fn nth_smallest_largest() {
+	let mut array = [NAN, 9.0, 1.0, NAN, 7.0, 3.0, NAN, 5.0];
+	assert_eq!(array.ord_subset_nth_smallest(0), Some(&1.0));
+	assert_eq!(array.ord_subset_nth_smallest(3), Some(&7.0));
+	assert_eq!(array.ord_subset_nth_smallest(4), Some(&9.0));
+	// only 5 values inside the order
+	assert_eq!(array.ord_subset_nth_smallest(5), None);
+	assert_eq!(array.ord_subset_nth_largest(0), Some(&9.0));
+	assert_eq!(array.ord_subset_nth_largest(4), Some(&1.0));
+	assert_eq!(array.ord_subset_nth_largest(5), None);
+	assert!(array[5..].iter().all(|x| x.is_nan()));
+	assert_eq!([NAN, NAN].ord_subset_nth_largest(0), None);
+}
+
+// ---------------------------- vec ext methods --------------------------------
+#[cfg(feature="std")]
+#[test]
+fn vec_dedup() {
+	let mut v = vec![1.0, 1.0, NAN, NAN];
+	v.ord_subset_dedup();
+	assert_eq!(v.len(), 2);
+	assert_eq!(v[0], 1.0);
+	assert!(v[1].is_nan());
+
+	let mut v = TEST_ARRAY.to_vec();
+	v.ord_subset_sort();
+	v.ord_subset_dedup();
+	let mut expected = SORTED_TEST_ARRAY_NO_NAN.to_vec();
+	expected.dedup();
+	assert_eq!(&v[..v.len()-1], &expected[..]);
+	assert!(v[v.len()-1].is_nan());
+
+	// non-consecutive duplicates are kept
+	let mut v = vec![1.0, NAN, 1.0, 2.0, 2.0];
+	v.ord_subset_dedup();
+	assert_eq!(v.len(), 4);
+}
 
-const SORTED_TEST_ARRAY_NO_NAN: [f64; N_NO_NAN] =
-	[-INF, 0.0,  1.0,  2.0,  3.0,  4.0,  5.0,  6.0,
-	 7.0,  8.0,  9.0, 10.0, 11.0, 12.0, 13.0, 14.0,
-	15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0,
-	23.0, 24.0, 25.0, 26.0, 27.0, INF             ];
+#[cfg(feature="std")]
+#[test]
+fn vec_dedup_by_key() {
+	let mut v = vec![(0, 1.0), (1, 1.0), (2, 2.0), (3, NAN), (4, NAN)];
+	v.ord_subset_dedup_by_key(|&(_, k)| k);
+	let ids: Vec<_> = v.iter().map(|&(id, _)| id).collect();
+	assert_eq!(ids, [0, 2, 3]);
+}
 
+#[cfg(feature="std")]
+#[test]
+fn vec_retain_ordered() {
+	let mut v = TEST_ARRAY.to_vec();
+	assert_eq!(v.ord_subset_retain_ordered(), N - N_NO_NAN);
+	assert_eq!(v, &TEST_ARRAY_NO_NAN[..]);
+	assert_eq!(v.ord_subset_retain_ordered(), 0);
+
+	let mut v = vec![NAN; 4];
+	assert_eq!(v.ord_subset_retain_ordered(), 4);
+	assert!(v.is_empty());
+}
 
-#[derive(PartialEq, PartialOrd, Clone, Copy)]
-struct NotOrdSub();
-#[derive(PartialEq, PartialOrd, Clone, Copy)]
-struct OrdSub();
+#[cfg(feature="std")]
+#[test]
+fn vec_retain_ordered_by_key() {
+	let mut v = vec![(0, 1.0), (1, NAN), (2, 2.0), (3, NAN)];
+	let mut n_calls = 0;
+	assert_eq!(v.ord_subset_retain_ordered_by_key(|&(_, k)| { n_calls += 1; k }), 2);
+	assert_eq!(n_calls, 4);
+	assert_eq!(v, [(0, 1.0), (2, 2.0)]);
+}
 
-impl OrdSubset for OrdSub {
-	fn is_outside_order(&self) -> bool {
-		true
+#[cfg(feature="std")]
+#[test]
+fn vec_insert_sorted() {
+	let mut v = vec![];
+	assert_eq!(v.ord_subset_insert_sorted(NAN), 0);
+	assert_eq!(v.ord_subset_insert_sorted(1.0), 0);
+	assert_eq!(v.ord_subset_insert_sorted(0.0), 0);
+	assert_eq!(v.ord_subset_insert_sorted(2.0), 2);
+	assert_eq!(v.ord_subset_insert_sorted(NAN), 4);
+	assert_eq!(&v[..3], &[0.0, 1.0, 2.0]);
+	assert!(v[3].is_nan() && v[4].is_nan());
+
+	let mut v = vec![];
+	for &num in TEST_ARRAY.iter() {
+		v.ord_subset_insert_sorted(num);
 	}
+	assert_eq!(&v[..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN[..]);
+	assert!(v[N_NO_NAN..].iter().all(|x| x.is_nan()));
 }
 
-// ---------------------------- iter ext methods -------------------------------
+#[cfg(feature="std")]
+#[test]
+fn vec_insert_sorted_by_key() {
+	let mut v = vec![(0, 1.0), (1, 2.0), (2, NAN)];
+	// equal keys go after existing ones
+	assert_eq!(v.ord_subset_insert_sorted_by_key((3, 1.0), |&(_, k)| k), 1);
+	assert_eq!(v.ord_subset_insert_sorted_by_key((4, NAN), |&(_, k)| k), 4);
+	assert_eq!(v.ord_subset_insert_sorted_by_key((5, -1.0), |&(_, k)| k), 0);
+	let ids: Vec<_> = v.iter().map(|&(id, _)| id).collect();
+	assert_eq!(ids, [5, 0, 3, 1, 2, 4]);
+}
 
+#[cfg(feature="std")]
 #[test]
-fn ord_subset_max() {
-	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
-	let max = arr.iter().ord_subset_max().unwrap();
-	assert_eq!(&5.0, max);
+fn vec_drain_unordered() {
+	let mut v = TEST_ARRAY.to_vec();
+	let drained: Vec<f64> = v.ord_subset_drain_unordered().collect();
+	assert_eq!(drained.len(), N - N_NO_NAN);
+	assert!(drained.iter().all(|x| x.is_nan()));
+	assert_eq!(v, &TEST_ARRAY_NO_NAN[..]);
+	v.ord_subset_sort();
+	assert_eq!(v, &SORTED_TEST_ARRAY_NO_NAN[..]);
+
+	// dropping the iterator early still removes everything
+	let mut v = TEST_ARRAY.to_vec();
+	v.ord_subset_drain_unordered().next();
+	assert_eq!(v, &TEST_ARRAY_NO_NAN[..]);
 }
 
+#[cfg(feature="std")]
 #[test]
-fn ord_subset_max_by() {
-	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
-	let max_by = arr.iter().ord_subset_max_by_key(|num| num.recip()).unwrap();
-	assert_eq!(&2.0, max_by);
+fn vec_drain_unordered_by_key() {
+	let mut v = vec![(0, NAN), (1, 1.0), (2, NAN), (3, 2.0)];
+	let mut n_calls = 0;
+	let drained: Vec<_> = v.ord_subset_drain_unordered_by_key(|&(_, k)| { n_calls += 1; k })
+		.map(|(id, _)| id)
+		.collect();
+	assert_eq!(n_calls, 4);
+	assert_eq!(drained, [0, 2]);
+	assert_eq!(v, [(1, 1.0), (3, 2.0)]);
 }
 
+#[cfg(feature="std")]
 #[test]
-fn ord_subset_min() {
-	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
-	let min = arr.iter().ord_subset_min().unwrap();
-	assert_eq!(&2.0, min);
+fn vec_pop_max_min() {
+	let mut v = TEST_ARRAY.to_vec();
+	let mut popped = vec![];
+	while let Some(max) = v.ord_subset_pop_max() {
+		popped.push(max);
+	}
+	popped.reverse();
+	assert_eq!(popped, &SORTED_TEST_ARRAY_NO_NAN[..]);
+	// unordered values remain
+	assert_eq!(v.len(), N - N_NO_NAN);
+	assert!(v.iter().all(|x| x.is_nan()));
+
+	let mut v = TEST_ARRAY.to_vec();
+	let mut popped = vec![];
+	while let Some(min) = v.ord_subset_pop_min() {
+		popped.push(min);
+	}
+	assert_eq!(popped, &SORTED_TEST_ARRAY_NO_NAN[..]);
+	assert_eq!(v.len(), N - N_NO_NAN);
 }
 
+#[cfg(feature="std")]
 #[test]
-fn ord_subset_min_by() {
-	let arr = [2.0, 3.0, 5.0, std::f64::NAN];
-	let min_by = arr.iter().ord_subset_min_by_key(|num| num.recip()).unwrap();
-	assert_eq!(&5.0, min_by);
+fn vec_pop_max_min_by_key() {
+	let mut v = vec![(0, 1.0), (1, NAN), (2, 3.0), (3, 3.0), (4, 1.0)];
+	assert_eq!(v.ord_subset_pop_max_by_key(|&(_, k)| k), Some((3, 3.0)));
+	assert_eq!(v.ord_subset_pop_min_by_key(|&(_, k)| k), Some((0, 1.0)));
+	assert_eq!(v.ord_subset_pop_min_by_key(|&(_, k)| k), Some((4, 1.0)));
+	assert_eq!(v.ord_subset_pop_max_by_key(|&(_, k)| k), Some((2, 3.0)));
+	assert_eq!(v.ord_subset_pop_max_by_key(|&(_, k)| k), None);
+	assert_eq!(v.len(), 1);
 }
 
-// This is a compile time test. It can't fail at runtime.
-// The referenced functions must accept iters of values, that are not OrdSubset
-// if the closure produces OrdSubset values
-#[allow(unused)]
-fn ord_subset_min_or_max_by_key() {
-	let array: [NotOrdSub; 0] = [];
-	array.iter().ord_subset_min_by_key(|_| 0.0);
-	array.iter().ord_subset_max_by_key(|_| 0.0);
+// ------------------------- vec deque ext methods -----------------------------
+// A deque with the given elements that wraps around its buffer after `n_front` elements
+#[cfg(feature="std")]
+fn wrapped_deque<T: Clone>(elements: &[T], n_front: usize) -> std::collections::VecDeque<T> {
+	let mut deque = std::collections::VecDeque::with_capacity(elements.len());
+	deque.extend(elements[n_front..].iter().cloned());
+	for el in elements[..n_front].iter().rev() {
+		deque.push_front(el.clone());
+	}
+	deque
 }
 
-// ---------------------------slice ext methods --------------------------------
-// ----------------------------- stable sorts ----------------------------------
+#[cfg(feature="std")]
+#[test]
+fn vec_deque_binary_search() {
+	for n_front in 0..N + 1 {
+		let deque = wrapped_deque(&SORTED_TEST_ARRAY, n_front);
+		if n_front != 0 && n_front != N {
+			assert_eq!(deque.as_slices().0.len(), n_front);
+		}
+		for (idx, num) in SORTED_TEST_ARRAY_NO_NAN.iter().enumerate() {
+			let found = deque.ord_subset_binary_search(num).unwrap();
+			assert_eq!(SORTED_TEST_ARRAY[found], *num);
+			assert_eq!(deque.ord_subset_binary_search(num), SORTED_TEST_ARRAY.ord_subset_binary_search(num), "{} {}", n_front, idx);
+		}
+		for num in &[-INF, -1e10, 0.5, 1e10] {
+			assert_eq!(deque.ord_subset_binary_search(num), SORTED_TEST_ARRAY.ord_subset_binary_search(num));
+		}
+	}
+}
 
+#[cfg(feature="std")]
 #[test]
+fn vec_deque_binary_search_by_key() {
+	let elements = [(0, 1.0), (1, 2.0), (2, 3.0), (3, NAN)];
+	for n_front in 0..elements.len() + 1 {
+		let deque = wrapped_deque(&elements, n_front);
+		assert_eq!(deque.ord_subset_binary_search_by_key(&3.0, |&(_, k)| k), Ok(2));
+		assert_eq!(deque.ord_subset_binary_search_by_key(&2.5, |&(_, k)| k), Err(2));
+		assert_eq!(deque.ord_subset_binary_search_by_key(&4.0, |&(_, k)| k), Err(3));
+	}
+}
+
 #[cfg(feature="std")]
-fn sort() {
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort();
-	assert_eq!(&array[0..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN);
+#[test]
+#[should_panic]
+fn vec_deque_binary_search_outside_order() {
+	let deque = wrapped_deque(&SORTED_TEST_ARRAY, 3);
+	let _ = deque.ord_subset_binary_search(&NAN);
 }
 
+#[cfg(feature="std")]
 #[test]
+fn vec_deque_sort_unstable() {
+	let mut deque = wrapped_deque(&TEST_ARRAY, 10);
+	deque.ord_subset_sort_unstable();
+	assert!(deque.iter().take(N_NO_NAN).eq(SORTED_TEST_ARRAY_NO_NAN.iter()));
+	assert!(deque.iter().skip(N_NO_NAN).all(|x| x.is_nan()));
+
+	let mut deque = wrapped_deque(&[(0, 2.0), (1, NAN), (2, 1.0)], 2);
+	deque.ord_subset_sort_unstable_by_key(|&(_, k)| k);
+	let ids: Vec<_> = deque.iter().map(|&(id, _)| id).collect();
+	assert_eq!(ids, [2, 0, 1]);
+}
+
 #[cfg(feature="std")]
-fn sort_rev() {
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_rev();
+#[test]
+fn vec_deque_max_min() {
+	let deque = wrapped_deque(&TEST_ARRAY, 10);
+	assert_eq!(deque.ord_subset_max(), TEST_ARRAY.ord_subset_max());
+	assert_eq!(deque.ord_subset_min(), TEST_ARRAY.ord_subset_min());
+
+	let deque = wrapped_deque(&[(0, 1.0), (1, NAN), (2, 3.0), (3, 3.0), (4, 1.0)], 3);
+	assert_eq!(deque.ord_subset_max_by_key(|&(_, k)| k), Some(&(3, 3.0)));
+	assert_eq!(deque.ord_subset_min_by_key(|&(_, k)| k), Some(&(0, 1.0)));
+}
 
-	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
-	rev_sorted_array.reverse();
+// -------------------------------- OrdVar -------------------------------------
 
-	assert_eq!(&array[0..N_NO_NAN], &rev_sorted_array);
+#[test]
+fn ord_var_try_new() {
+	assert_eq!(OrdVar::try_new(1.0), Ok(OrdVar::new(1.0)));
+	assert_eq!(OrdVar::try_new(INF).map(OrdVar::into_inner), Ok(INF));
+	match OrdVar::try_new(NAN) {
+		Err(num) => assert!(num.is_nan()),
+		Ok(_) => panic!("NAN is outside total order"),
+	}
 }
 
 #[test]
-#[cfg(feature="std")]
-fn sort_by_key() {
-	fn key_function(el: &f64) -> f64 {
-		(el - 13.0).recip()
+fn ord_var_from_str() {
+	use ord_subset::ParseOrdVarError;
+
+	assert_eq!("3.5".parse::<OrdVar<f64>>(), Ok(OrdVar::new(3.5)));
+	assert_eq!("inf".parse::<OrdVar<f64>>(), Ok(OrdVar::new(INF)));
+	assert_eq!("NaN".parse::<OrdVar<f64>>(), Err(ParseOrdVarError::OutsideOrder));
+	match "foo".parse::<OrdVar<f64>>() {
+		Err(ParseOrdVarError::Parse(_)) => {},
+		_ => panic!("parsing garbage succeeded"),
 	}
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_by_key(key_function);
-	let mut std_sorted_array = TEST_ARRAY_NO_NAN;
-	std_sorted_array.sort_by_key(|num| OrdVar::new(key_function(num)));
-	assert_eq!(&array[..N_NO_NAN], &std_sorted_array);
+	assert_eq!(ParseOrdVarError::<std::num::ParseFloatError>::OutsideOrder.to_string(), "parsed value is outside of total order");
 }
 
-// ----------------------------- unstable sorts --------------------------------
-
 #[test]
-fn sort_unstable() {
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable();
-	assert_eq!(&array[0..N_NO_NAN], &SORTED_TEST_ARRAY_NO_NAN);
+fn ord_var_fmt() {
+	let num = OrdVar::new(1234.5);
+	assert_eq!(format!("{}", num), "1234.5");
+	assert_eq!(format!("{:.2}", num), "1234.50");
+	assert_eq!(format!("{:e}", num), "1.2345e3");
+	assert_eq!(format!("{:E}", num), "1.2345E3");
 }
 
 #[test]
-fn sort_unstable_rev() {
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable_rev();
-
-	let mut rev_sorted_array = SORTED_TEST_ARRAY_NO_NAN;
-	rev_sorted_array.reverse();
+fn ord_var_fmt_int() {
+	let num = OrdVar::new(255u32);
+	assert_eq!(format!("{:b}", num), "11111111");
+	assert_eq!(format!("{:o}", num), "377");
+	assert_eq!(format!("{:x}", num), "ff");
+	assert_eq!(format!("{:08X}", num), "000000FF");
+	assert_eq!(format!("{:#x}", num), "0xff");
+}
 
-	assert_eq!(&array[0..N_NO_NAN], &rev_sorted_array);
+#[test]
+fn ord_var_total_cmp() {
+	use std::cmp::Ordering::*;
+	let nan = unsafe { OrdVar::new_unchecked(NAN) };
+	let neg_nan = unsafe { OrdVar::new_unchecked(-NAN) };
+	assert_eq!(nan.total_cmp(&nan), Equal);
+	assert_eq!(neg_nan.total_cmp(&OrdVar::new(-INF)), Less);
+	assert_eq!(OrdVar::new(INF).total_cmp(&nan), Less);
+	assert_eq!(OrdVar::new(0.0).total_cmp(&OrdVar::new(-0.0)), Greater);
+	assert_eq!(OrdVar::new(1.0f32).total_cmp(&OrdVar::new(2.0)), Less);
 }
 
 #[test]
-fn sort_unstable_by_key() {
-	fn key_function(el: &f64) -> f64 {
-		(el - 13.0).recip()
+fn ord_var_float_methods() {
+	let x = OrdVar::new(-4.0);
+	assert!(x.is_finite() && !x.is_infinite());
+	assert!(x.is_sign_negative() && !x.is_sign_positive());
+	assert!(OrdVar::new(INF).is_infinite());
+	#[cfg(feature="std")]
+	{
+		assert_eq!(x.abs(), OrdVar::new(4.0));
+		assert_eq!(x.abs().sqrt(), Some(OrdVar::new(2.0)));
+		assert_eq!(x.sqrt(), None);
+		assert_eq!(OrdVar::new(-0.0f32).sqrt(), Some(OrdVar::new(0.0)));
 	}
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable_by_key(key_function);
-	let mut std_sorted_array = TEST_ARRAY_NO_NAN;
-	std_sorted_array.sort_unstable_by_key(|num| OrdVar::new(key_function(num)));
-	assert_eq!(&array[..N_NO_NAN], &std_sorted_array);
 }
 
-// ---------------------------- binary searches --------------------------------
+#[test]
+fn ord_var_fmt_pointer() {
+	let x = 1.0f64;
+	let ptr: *const f64 = &x;
+	assert_eq!(format!("{:p}", OrdVar::from(ptr)), format!("{:p}", ptr));
+	assert_eq!(format!("{:p}", OrdVar::new(&x)), format!("{:p}", &x));
+}
 
 #[test]
-fn binary_search() {
-	let array = SORTED_TEST_ARRAY;
-	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
-		assert_eq!(array.ord_subset_binary_search(num), Ok(i));
+fn ord_var_default() {
+	#[derive(Default)]
+	struct Foo {
+		bar: OrdVar<f64>,
 	}
+	assert_eq!(Foo::default().bar.into_inner(), 0.0);
 }
 
 #[test]
-fn binary_search_rev() {
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable_rev();
-	for (i, num) in array.iter().enumerate().take(N_NO_NAN) {
-		assert_eq!(array.ord_subset_binary_search_rev(num), Ok(i));
-	}
+fn ord_var_cmp_inner() {
+	let num = OrdVar::new(5.0);
+	assert!(num == 5.0);
+	assert!(num != 3.0);
+	assert!(num > 3.0);
+	assert!(num <= INF);
+	assert_eq!(num.partial_cmp(&NAN), None);
+	assert!(num == OrdVar::new(5.0));
 }
 
 #[test]
-fn binary_search_by_key() {
-	fn key_function(el: &f64) -> f64 {
-		(el - 13.0).recip()
-	}
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable_by_key(key_function);
-	for num in array.iter().take(N_NO_NAN) {
-		let key = key_function(num);
-		match array.ord_subset_binary_search_by_key(&key, key_function) {
-			Err(_) => panic!("Did not find correct location of element"),
-			Ok(pos) => assert_eq!(key_function(&array[pos]), key),
-		}
-	}
+fn ord_var_new_unchecked() {
+	let num = unsafe { OrdVar::new_unchecked(5.0) };
+	assert_eq!(num, OrdVar::new(5.0));
 }
 
-// ------ binary search error cases ------
+#[test]
+fn ord_var_index() {
+	let ord_array = OrdVar::new([1.0, 2.0, 3.0]);
+	assert_eq!(ord_array[1], 2.0);
+	assert_eq!(&ord_array[1..], &[2.0, 3.0]);
+	let ord_slice = OrdVar::new(&SORTED_TEST_ARRAY_NO_NAN);
+	assert_eq!(ord_slice[0], SORTED_TEST_ARRAY_NO_NAN[0]);
+}
 
 #[test]
-fn binary_search_err() {
-	let array = SORTED_TEST_ARRAY;
-	for (i, num) in array.iter()
-		.enumerate()
-		.filter(|&(_, num)| num.is_finite())
-	{
-		let new_num = num + 0.5;
-		assert_eq!(array.ord_subset_binary_search(&new_num), Err(i+1));
-	}
+fn ord_var_from_ord() {
+	let key: OrdVar<u64> = 42u64.into();
+	assert_eq!(key, OrdVar::from(42u64));
+	assert_eq!(*OrdVar::from((1u8, 'a')), (1, 'a'));
 }
 
 #[test]
-fn binary_search_rev_err() {
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable_rev();
-	for (i, num) in array.iter()
-		.enumerate()
-		.filter(|&(_, num)| num.is_finite())
-	{
-		let new_num = num + 0.5;
-		assert_eq!(array.ord_subset_binary_search_rev(&new_num), Err(i));
-	}
+fn ord_var_neg() {
+	assert_eq!(-OrdVar::new(5.0), OrdVar::new(-5.0));
+	assert_eq!(-OrdVar::new(-INF), OrdVar::new(INF));
+	assert_eq!(-OrdVar::new(3i32), OrdVar::new(-3));
 }
 
 #[test]
-fn binary_search_by_key_err() {
-	fn key_function(el: &f64) -> f64 {
-		(el - 13.0).recip()
-	}
-	let mut array = TEST_ARRAY;
-	array.ord_subset_sort_unstable_by_key(key_function);
-	for num in array.iter().take(N_NO_NAN) {
-		let key_diff = key_function(&(num+0.01))*1.01 + 0.01;
-		let pos = array.ord_subset_binary_search_by_key(&key_diff, key_function);
-		let pos_std = (&array[..N_NO_NAN]).binary_search_by_key(
-			&OrdVar::new(key_diff),
-			|num| OrdVar::new(key_function(num))
-		);
-		match (pos, pos_std) {
-			(Err(pos), Err(pos_std)) => assert!(pos == pos_std),
-			// the commented out match branch is also valid behaviour
-			// but this function is supposed to test as many error cases as possible
-			// by choosing key_diff the right way
-			//(Ok(pos), Ok(pos_std)) => {
-			//	let key1 = key_function(&array[pos]);
-			//	let key2 = key_function(&array[pos_std]);
-			//	assert!(key1 == key2);
-			//},
-			_ => panic!("Inconsistency between this library's and std's binary_search_by_key"),
-		}
+fn ord_var_into_iter() {
+	let ord_slice = OrdVar::new(&SORTED_TEST_ARRAY_NO_NAN);
+	let mut n = 0;
+	for (&a, &b) in ord_slice.into_iter().zip(SORTED_TEST_ARRAY_NO_NAN.iter()) {
+		assert_eq!(a, b);
+		n += 1;
 	}
+	assert_eq!(n, N_NO_NAN);
+	assert_eq!(OrdVar::new(&SORTED_TEST_ARRAY_NO_NAN).into_iter().ord_subset_max(), Some(&INF));
 }
 
 // -------------------- compile time implementation tests ----------------------
@@ -302,12 +1788,22 @@ fn ord_subset_mut_slice_ext_impl_test() {
 		#[cfg(feature="std")]
 		as_slice.ord_subset_sort_by(|_, _| core::cmp::Ordering::Equal);
 		#[cfg(feature="std")]
+		as_slice.ord_subset_sort_rev_by(|_, _| core::cmp::Ordering::Equal);
+		#[cfg(feature="std")]
 		as_slice.ord_subset_sort_by_key(|_| 0.0);
+		#[cfg(feature="std")]
+		as_slice.ord_subset_sort_rev_by_key(|_| 0.0);
 
 		as_slice.ord_subset_sort_unstable();
 		as_slice.ord_subset_sort_unstable_rev();
 		as_slice.ord_subset_sort_unstable_by(|_, _| core::cmp::Ordering::Equal);
+		as_slice.ord_subset_sort_unstable_rev_by(|_, _| core::cmp::Ordering::Equal);
 		as_slice.ord_subset_sort_unstable_by_key(|_| 0.0);
+		as_slice.ord_subset_sort_unstable_rev_by_key(|_| 0.0);
+
+		#[cfg(feature="std")]
+		as_slice.ord_subset_sort_with(UnorderedPolicy::Front);
+		as_slice.ord_subset_sort_unstable_with(UnorderedPolicy::Front);
 	}
 
 	let mut vec: Vec<OrdSub> = vec![];
@@ -339,6 +1835,11 @@ fn non_ord_subset_slice_ext_impl_test() {
 	fn foo<T: OrdSubsetSliceExt<U> + AsRef<[U]>, U>(as_slice: T) {
 		let key = OrdSub();
 		as_slice.ord_subset_binary_search_by_key(&key, |_| key);
+		as_slice.ord_subset_max_by_key(|_| key);
+		as_slice.ord_subset_min_by_key(|_| key);
+		as_slice.ord_subset_max_index_by_key(|_| key);
+		as_slice.ord_subset_min_index_by_key(|_| key);
+		as_slice.ord_subset_extrema_by_key(|_| key);
 	}
 
 	let mut vec: Vec<NotOrdSub> = vec![];
@@ -376,8 +1877,11 @@ fn non_ord_subset_mut_slice_ext_impl_test() {
 
 		#[cfg(feature="std")]
 		as_slice.ord_subset_sort_by_key(|_| key);
+		#[cfg(feature="std")]
+		as_slice.ord_subset_sort_rev_by_key(|_| key);
 
 		as_slice.ord_subset_sort_unstable_by_key(|_| key);
+		as_slice.ord_subset_sort_unstable_rev_by_key(|_| key);
 	}
 
 	let mut vec: Vec<NotOrdSub> = vec![];
@@ -454,6 +1958,14 @@ fn ops_correctness_test() {
 		//ShlAssign::shl_assign, ShrAssign::shr_assign
 	];
 
+	let infix_ops_ordvar_rhs = [
+		Add::add, Sub::sub, Mul::mul, Div::div, Rem::rem,
+	];
+
+	let assign_ops_ordvar_rhs = [
+		AddAssign::add_assign, SubAssign::sub_assign, MulAssign::mul_assign, DivAssign::div_assign, RemAssign::rem_assign,
+	];
+
 	// skip 0, can't divide by it
 	let nums = (-10..0).chain(1..11i32).map(|n| n as f64).collect::<Vec<_>>();
 	let combinations = nums.iter().flat_map(|&n1| nums.iter().map(move |&n2| (n1, n2)));
@@ -467,6 +1979,13 @@ fn ops_correctness_test() {
 			assert!(res == res2.into_inner())
 		}
 
+		// infix ops with OrdVar on both sides
+		for (op, op_ordvar) in infix_ops.iter().zip(infix_ops_ordvar_rhs.iter()) {
+			let res = op(num1, num2);
+			let res2 = op_ordvar(OrdVar::new(num1), OrdVar::new(num2));
+			assert!(res == res2.into_inner())
+		}
+
 		// unary ops
 		for (op, op_ordvar) in unary_ops.iter().zip(unary_ops_ordvar.iter()) {
 			let res = op(num1);
@@ -483,5 +2002,106 @@ fn ops_correctness_test() {
 			op_ordvar(&mut ordvar, num2);
 			assert!(num1 == ordvar.into_inner())
 		}
+
+		// assign ops with OrdVar on both sides
+		for (op, op_ordvar) in assign_ops.iter().zip(assign_ops_ordvar_rhs.iter()) {
+			let mut num1 = num1;
+			let mut ordvar = OrdVar::new(num1);
+			op(&mut num1, num2);
+			op_ordvar(&mut ordvar, OrdVar::new(num2));
+			assert!(num1 == ordvar.into_inner())
+		}
+	}
+}
+
+#[test]
+#[cfg(feature="ops")]
+fn ops_ordvar_rhs_integers() {
+	macro_rules! check {
+		($($T:ident),*) => {$(
+			let (a, b): ($T, $T) = (7, 3);
+			assert_eq!((OrdVar::new(a) + OrdVar::new(b)).into_inner(), a + b);
+			assert_eq!((OrdVar::new(a) - OrdVar::new(b)).into_inner(), a - b);
+			assert_eq!((OrdVar::new(a) * OrdVar::new(b)).into_inner(), a * b);
+			assert_eq!((OrdVar::new(a) / OrdVar::new(b)).into_inner(), a / b);
+			assert_eq!((OrdVar::new(a) % OrdVar::new(b)).into_inner(), a % b);
+			let mut x = OrdVar::new(a);
+			x += OrdVar::new(b);
+			x *= OrdVar::new(b);
+			assert_eq!(x.into_inner(), (a + b) * b);
+		)*};
+	}
+	check!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+}
+
+#[test]
+fn ord_var_wrap_slice() {
+	let wrapped = OrdVar::wrap_slice(&SORTED_TEST_ARRAY_NO_NAN).unwrap();
+	assert_eq!(wrapped.len(), SORTED_TEST_ARRAY_NO_NAN.len());
+	assert!(wrapped.iter().zip(SORTED_TEST_ARRAY_NO_NAN.iter()).all(|(a, b)| a == b));
+	assert_eq!(OrdVar::wrap_slice(&TEST_ARRAY), None);
+	assert_eq!(OrdVar::<f64>::wrap_slice(&[]), Some(&[][..]));
+}
+
+#[test]
+#[cfg(feature="bytemuck")]
+fn bytemuck_cast() {
+	use bytemuck::Zeroable;
+
+	let wrapped: Vec<OrdVar<f64>> = SORTED_TEST_ARRAY_NO_NAN.iter().cloned().map(OrdVar::new).collect();
+	let cast: &[f64] = bytemuck::cast_slice(&wrapped);
+	assert_eq!(cast, &SORTED_TEST_ARRAY_NO_NAN[..]);
+	assert_eq!(OrdVar::<f64>::zeroed(), 0.0);
+	assert_eq!(OrdVar::<u32>::zeroed(), 0);
+}
+
+#[test]
+#[cfg(feature="ops")]
+fn ops_sum_product() {
+	let nums: Vec<_> = [1.0, 2.0, 3.0, 4.0].iter().cloned().map(OrdVar::new).collect();
+	assert_eq!(nums.iter().sum::<OrdVar<f64>>(), 10.0);
+	assert_eq!(nums.iter().product::<OrdVar<f64>>(), 24.0);
+	assert_eq!(nums.into_iter().sum::<OrdVar<f64>>(), 10.0);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(feature="ops", not(feature="unchecked_ops")))]
+fn ops_sum_outside_order() {
+	let _ = [INF, -INF].iter().cloned().map(OrdVar::new).sum::<OrdVar<f64>>();
+}
+
+#[test]
+#[cfg(feature="rust_decimal")]
+fn rust_decimal_sort() {
+	use rust_decimal::Decimal;
+	let mut array = [Decimal::new(314, 2), Decimal::new(-1, 0), Decimal::new(27, 1)];
+	array.ord_subset_sort_unstable();
+	assert_eq!(array, [Decimal::new(-1, 0), Decimal::new(27, 1), Decimal::new(314, 2)]);
+	assert_eq!(array.ord_subset_binary_search(&Decimal::new(270, 2)), Ok(1));
+	assert_eq!(array.iter().ord_subset_max(), Some(&Decimal::new(314, 2)));
+}
+
+#[test]
+#[cfg(feature="serde")]
+fn serde_deserialize() {
+	use serde::de::{Deserialize, IntoDeserializer};
+	use serde::de::value::{Error, F64Deserializer};
+
+	fn deserialize(num: f64) -> Result<OrdVar<f64>, Error> {
+		let deserializer: F64Deserializer<Error> = num.into_deserializer();
+		OrdVar::deserialize(deserializer)
 	}
+	assert_eq!(deserialize(1.5), Ok(OrdVar::new(1.5)));
+	assert_eq!(deserialize(-INF), Ok(OrdVar::new(-INF)));
+	assert!(deserialize(NAN).is_err());
+}
+
+// This is a compile time test.
+#[cfg(feature="serde")]
+#[allow(unused)]
+fn serde_serialize_impl_test() {
+	fn is_serialize<T: serde::Serialize>() {}
+	is_serialize::<OrdVar<f64>>();
+	is_serialize::<OrdVar<(u8, f32)>>();
 }